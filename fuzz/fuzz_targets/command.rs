@@ -0,0 +1,13 @@
+//! A move typed as a command is the same move as typed on its own.
+
+#![no_main]
+
+use guessing_game::coordinates::digit_layout;
+use guessing_game::{parse_move, Command};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(Command::Select(coord)) = Command::parse(input) {
+        assert!(parse_move(input, digit_layout()).ok() == Some(coord), "{} is not the move typed", coord);
+    }
+});
@@ -0,0 +1,12 @@
+//! Any config file is read without panicking, and so are the section names of one that is accepted.
+
+#![no_main]
+
+use guessing_game::Config;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(config) = Config::parse(input) {
+        config.sections("");
+    }
+});
@@ -0,0 +1,16 @@
+//! A move typed in any digit layout either fails to read or reads back as the same cell from the way it
+//! is shown.
+
+#![no_main]
+
+use guessing_game::coordinates::DigitLayout;
+use guessing_game::parse_move;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for layout in DigitLayout::ALL {
+        if let Ok(coord) = parse_move(input, layout) {
+            assert!(parse_move(&coord.to_string(), layout).ok() == Some(coord), "{} does not read back", coord);
+        }
+    }
+});
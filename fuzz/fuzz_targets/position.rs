@@ -0,0 +1,14 @@
+//! A position either fails to read or reads back as the same cells and turn from its compact form.
+
+#![no_main]
+
+use guessing_game::Board;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(board) = Board::from_compact(input) {
+        let compact = board.to_compact_string();
+        let again = Board::from_compact(&compact).map(Board::to_compact_string);
+        assert!(again.ok().as_ref() == Some(&compact), "{} does not read back", compact);
+    }
+});
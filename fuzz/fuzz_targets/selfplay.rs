@@ -0,0 +1,14 @@
+//! Every line of an accepted self-play file is one move of a game.
+
+#![no_main]
+
+use guessing_game::parse_games;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(games) = parse_games(input, "fuzz") {
+        let moves: usize = games.iter().map(|game| game.moves.len()).sum();
+        let lines = input.lines().filter(|line| !line.trim().is_empty()).count();
+        assert_eq!(moves, lines, "moves read from the lines of the file");
+    }
+});
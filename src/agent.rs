@@ -0,0 +1,137 @@
+//! Named bot configurations, chosen for each side with `--p1` and `--p2`. They are defined in
+//! `[agent.<name>]` sections of the config file:
+//!
+//! ```text
+//! [agent.quick]
+//! ai = minimax
+//! depth = 2
+//! time_ms = 50
+//! blunder_rate = 0.1
+//! book = true
+//! seed = 42
+//! handicap = no-center
+//! ```
+//!
+//! The built-in difficulty names work as agents too, and `human` puts a person in the seat.
+
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::error::ParseError;
+use crate::i18n::tr_args;
+use crate::handicap::Handicap;
+use crate::options::{parse_count, parse_handicaps, parse_probability, parse_strategy, Options};
+use crate::strategy::Strategy;
+
+/// Who plays one side of the game.
+#[derive(Debug)]
+pub enum Seat {
+    Human,
+    Bot(Agent),
+}
+
+#[derive(Debug, Clone)]
+pub struct Agent {
+    pub name: String,
+    pub strategy: Strategy,
+    /// None follows the player's profile, like the adaptive difficulty.
+    pub blunder_rate: Option<f64>,
+    /// How many plies the minimax search looks ahead. None searches to the end of the game.
+    pub max_depth: Option<i32>,
+    /// Time the minimax search may spend per move, deepening one ply at a time.
+    pub time_budget: Option<Duration>,
+    /// Play the first two plies from the opening book instead of searching.
+    pub book: bool,
+    /// Seed for the bot's own random choices, to make its games repeatable.
+    pub seed: Option<u64>,
+    pub epsilon: f64,
+    pub weights: Option<String>,
+    /// Rules holding back the bot's first move, for beginners.
+    pub handicaps: Vec<Handicap>,
+}
+
+impl Agent {
+    /// The bot set up by the global `--difficulty`, `--ai` and related options.
+    pub fn from_options(options: &Options) -> Agent {
+        Agent::with_options(options.difficulty, options)
+    }
+
+    /// A plain minimax bot at the given difficulty.
+    pub fn from_difficulty(difficulty: Difficulty) -> Agent {
+        Agent {
+            name: difficulty.name().to_string(),
+            strategy: Strategy::Minimax,
+            blunder_rate: difficulty.blunder_rate(),
+            max_depth: None,
+            time_budget: None,
+            book: false,
+            seed: None,
+            epsilon: 1.0,
+            weights: None,
+            handicaps: Vec::new(),
+        }
+    }
+
+    fn with_options(difficulty: Difficulty, options: &Options) -> Agent {
+        Agent {
+            strategy: options.strategy,
+            epsilon: options.epsilon,
+            weights: options.weights.clone(),
+            handicaps: options.handicaps.clone(),
+            ..Agent::from_difficulty(difficulty)
+        }
+    }
+
+    /// Loads the `[agent.<name>]` section of the config file.
+    fn from_config(name: &str, config: &Config) -> Result<Agent, ParseError> {
+        let key = |field: &str| config.get(&format!("agent.{}.{}", name, field));
+        let mut agent = Agent {
+            name: name.to_string(),
+            book: key("book") == Some("true"),
+            weights: key("weights").map(str::to_string),
+            ..Agent::from_difficulty(Difficulty::Perfect)
+        };
+
+        if let Some(value) = key("ai") {
+            agent.strategy = parse_strategy(value)?;
+        }
+        if let Some(value) = key("blunder_rate") {
+            agent.blunder_rate = Some(parse_probability(value)?);
+        }
+        if let Some(value) = key("epsilon") {
+            agent.epsilon = parse_probability(value)?;
+        }
+        if let Some(value) = key("depth") {
+            let depth = parse_count(value)?;
+            let depth = i32::try_from(depth)
+                .map_err(|_| ParseError::new(&tr_args("error.invalid_count", &[("value", &value)])))?;
+            agent.max_depth = Some(depth);
+        }
+        if let Some(value) = key("time_ms") {
+            agent.time_budget = Some(Duration::from_millis(parse_count(value)?));
+        }
+        if let Some(value) = key("seed") {
+            agent.seed = Some(parse_count(value)?);
+        }
+        if let Some(value) = key("handicap") {
+            agent.handicaps = parse_handicaps(value)?;
+        }
+        Ok(agent)
+    }
+}
+
+/// Resolves a `--p1`/`--p2` value: `human`, an agent from the config file, or a difficulty name.
+pub fn parse_seat(name: &str, config: &Config, options: &Options) -> Result<Seat, ParseError> {
+    let name = name.trim();
+    if name == "human" {
+        return Ok(Seat::Human);
+    }
+    if config.has_section(&format!("agent.{}", name)) {
+        return Agent::from_config(name, config).map(Seat::Bot);
+    }
+    match Difficulty::from_name(name) {
+        Some(difficulty) => Ok(Seat::Bot(Agent::with_options(difficulty, options))),
+        None => Err(ParseError::new(&tr_args("error.unknown_agent", &[("name", &name)]))),
+    }
+}
@@ -0,0 +1,103 @@
+use crate::i18n::{tr, tr_args};
+use crate::{evaluate, Board, Coordinate, Player};
+
+/// How many winning lines pass through a cell: 4 for the center, 3 for corners, 2 for edges.
+pub fn lines_through(index: usize) -> usize {
+    Board::lines().filter(|line| line.contains(&index)).count()
+}
+
+/// Empty cells that would complete a line for the player right away.
+pub fn winning_cells(board: &Board, player: Player) -> Vec<usize> {
+    let mut cells: Vec<usize> = Board::lines()
+        .filter_map(|line| {
+            let contents = board.line_cells(line);
+            let owned = contents.iter().filter(|&&cell| cell == Some(player)).count();
+            let empty = contents.iter().position(|cell| cell.is_none());
+            if owned == 2 { empty.map(|position| line[position]) } else { None }
+        })
+        .collect();
+    cells.sort_unstable();
+    cells.dedup();
+    cells
+}
+
+/// Empty cells that would give the player two or more winning cells at once, which the opponent can't both block.
+pub fn fork_cells(board: &Board, player: Player) -> Vec<usize> {
+    (0..9)
+        .filter(|&cell| board.cells[cell].is_none())
+        .filter(|&cell| {
+            let mut after = *board;
+            after.cells[cell] = Some(player);
+            winning_cells(&after, player).len() >= 2
+        })
+        .collect()
+}
+
+/// Formats cell indices as a list of coordinates, e.g. "1-3, 2-2".
+pub fn format_cells(cells: &[usize]) -> String {
+    cells.iter().filter_map(|&cell| Coordinate::from_index(cell)).map(|coord| coord.to_string()).collect::<Vec<String>>().join(", ")
+}
+
+/// Lists every immediate winning cell and fork cell for both sides.
+pub fn explain(board: &Board) -> String {
+    let mut text = String::new();
+    for player in [Player::O, Player::X] {
+        let describe = |cells: Vec<usize>| if cells.is_empty() { tr("explain.none").to_string() } else { format_cells(&cells) };
+        text.push_str(&tr_args(
+            "explain.side",
+            &[
+                ("player", &player.name()),
+                ("wins", &describe(winning_cells(board, player))),
+                ("forks", &describe(fork_cells(board, player))),
+            ],
+        ));
+        text.push('\n');
+    }
+    text
+}
+
+/// How closely the player's moves kept to perfect play, from 0 to 100. A move scores 100 if it keeps
+/// the best outcome the player could still force, 50 if it drops a win to a draw or a draw to a loss,
+/// and 0 if it throws a win away entirely. None if the player made no moves.
+pub fn accuracy(start: &Board, moves: &[Coordinate], player: Player) -> Option<f64> {
+    let mut board = *start;
+    let mut scores = Vec::new();
+    for &coord in moves {
+        let mover = board.turn;
+        let best = if mover == player { Some(evaluate(&board, player).signum()) } else { None };
+        board.play_move(coord);
+        board.turn = board.turn.opponent();
+        board.check_if_game_over();
+        if let Some(best) = best {
+            scores.push(100.0 - 50.0 * (best - evaluate(&board, player).signum()) as f64);
+        }
+    }
+    if scores.is_empty() { None } else { Some(scores.iter().sum::<f64>() / scores.len() as f64) }
+}
+
+/// The result the player to move can force after playing each empty cell: 1 win, 0 draw, -1 loss.
+pub fn move_values(board: &Board) -> [Option<i32>; 9] {
+    let mut values = [None; 9];
+    for coord in board.empty_cells() {
+        let mut after = *board;
+        after.play_move(coord);
+        after.turn = after.turn.opponent();
+        after.check_if_game_over();
+        values[coord.index()] = Some(evaluate(&after, board.turn).signum());
+    }
+    values
+}
+
+/// The best moves for the player to move and what they lead to.
+pub fn hint(values: &[Option<i32>; 9]) -> String {
+    let Some(best) = values.iter().flatten().max().copied() else {
+        return tr("hint.none").to_string();
+    };
+    let cells: Vec<usize> = (0..9).filter(|&cell| values[cell] == Some(best)).collect();
+    let key = match best {
+        1 => "hint.win",
+        0 => "hint.draw",
+        _ => "hint.loss",
+    };
+    tr_args(key, &[("cells", &format_cells(&cells))])
+}
@@ -0,0 +1,512 @@
+//! Every finished game, kept in `games` under the data directory to look back over later:
+//!
+//! ```text
+//! games list
+//! games show <id>
+//! games explore <id>
+//! games search [--player <name>] [--result win|draw|loss|x|o] [--opening <row-col>]
+//! games verify
+//! games log <id>
+//! ```
+//!
+//! Games are appended to the file in the format of the config file, one section each, after the version of
+//! the format, see `save_format`:
+//!
+//! ```text
+//! format = 1
+//!
+//! [game.3]
+//! x = perfect
+//! o = Alice
+//! human = O
+//! started = 1760536800
+//! ended = 1760536830
+//! moves = 1-1 2-2 3-3 1-3 3-1 2-1 1-2
+//! winner = O
+//! termination = line
+//! accuracy = 83
+//! checksum = 6f1c2a4e90b3d857
+//! ```
+//!
+//! `checksum` is a hash of the rest of the section, so that `games verify` and the leaderboard can tell
+//! a result edited by hand. It catches edits, not forgeries: anyone with the source can compute it.
+//! `takebacks` counts the move pairs taken back during the game, when there were any. `difficulty` is the level
+//! of the bot when a human played the usual bot. `times` holds the seconds each move took, `-` where it isn't known.
+//!
+//! Lines tried after the game with `games explore` are appended in sections of their own, numbered per game.
+//! `from` is how many moves of the game were played before the variation:
+//!
+//! ```text
+//! [variation.3.1]
+//! from = 2
+//! moves = 1-3 3-1 2-1
+//! ```
+//!
+//! `start` holds the position the game began from, when it wasn't the empty board.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::analysis;
+use crate::config::{data_dir, Config};
+use crate::difficulty::Difficulty;
+use crate::error::{GameError, IoError, ParseError};
+use crate::event_log;
+use crate::explore::{self, Variation};
+use crate::game_result::{GameResult, Termination};
+use crate::i18n::{tr, tr_args};
+use crate::options::{flag_value, parse_count};
+use crate::profile::Outcome;
+use crate::save_format;
+use crate::{parse_coordinates, say, Board, Coordinate, Player, Winner};
+
+#[derive(Clone)]
+pub struct SavedGame {
+    pub id: u64,
+    /// Names of the players, indexed by player.
+    pub players: [String; 2],
+    /// The side of the human, when a human played a bot.
+    pub human: Option<Player>,
+    /// Seconds since the Unix epoch.
+    pub started: u64,
+    pub ended: u64,
+    /// The position the game began from, in the `--position` format. None for the empty board.
+    pub start: Option<String>,
+    pub moves: Vec<Coordinate>,
+    pub winner: Winner,
+    pub termination: Termination,
+    /// How well the human played, from 0 to 100, see `analysis::accuracy`.
+    pub accuracy: Option<f64>,
+    /// Move pairs taken back during the game.
+    pub takebacks: usize,
+    /// How strong the bot played, for a human playing the usual bot rather than `--p1` and `--p2`.
+    pub difficulty: Option<Difficulty>,
+    /// How long each move took to choose, None where it isn't known, such as the moves before a game was resumed.
+    pub times: Vec<Option<Duration>>,
+    /// Lines tried instead of the game's moves, kept apart from them.
+    pub variations: Vec<Variation>,
+    /// The checksum the game was stored with, None for games saved before there were checksums.
+    pub checksum: Option<u64>,
+}
+
+impl SavedGame {
+    /// The game that just finished on the board. The id is given when it's saved.
+    pub fn new(board: &Board, result: &GameResult, start: Option<String>, human: Option<Player>, started: SystemTime) -> SavedGame {
+        let moves: Vec<Coordinate> = board.history[..board.moves].iter().flatten().map(|&(_, coord)| coord).collect();
+        let accuracy = human.and_then(|side| {
+            let start = start.as_deref().map_or(Ok(Board::new()), Board::from_compact).ok()?;
+            analysis::accuracy(&start, &moves, side)
+        });
+        SavedGame {
+            id: 0,
+            players: [Player::X.name(), Player::O.name()],
+            human,
+            started: seconds(started),
+            ended: seconds(SystemTime::now()),
+            start,
+            moves,
+            winner: result.winner,
+            termination: result.termination,
+            accuracy,
+            takebacks: board.takebacks,
+            difficulty: None,
+            times: Vec::new(),
+            variations: Vec::new(),
+            checksum: None,
+        }
+    }
+
+    /// Reads the `[game.<id>]` section of the archive.
+    fn from_config(id: &str, config: &Config) -> Option<SavedGame> {
+        let key = |field: &str| config.get(&format!("game.{}.{}", id, field));
+        let moves = match key("moves") {
+            Some(moves) => moves.split_whitespace().map(|coord| parse_coordinates(coord.to_string()).ok()).collect::<Option<_>>()?,
+            None => Vec::new(),
+        };
+        Some(SavedGame {
+            id: id.parse().ok()?,
+            players: [key("x")?.to_string(), key("o")?.to_string()],
+            human: key("human").and_then(parse_player),
+            started: key("started")?.parse().ok()?,
+            ended: key("ended")?.parse().ok()?,
+            start: key("start").map(str::to_string),
+            moves,
+            winner: match key("winner")? {
+                "draw" => Winner::Draw,
+                player => Winner::Player(parse_player(player)?),
+            },
+            termination: Termination::from_name(key("termination")?)?,
+            accuracy: key("accuracy").and_then(|accuracy| accuracy.parse().ok()),
+            takebacks: key("takebacks").and_then(|count| count.parse().ok()).unwrap_or(0),
+            difficulty: key("difficulty").and_then(Difficulty::from_name),
+            times: key("times").map_or(Vec::new(), |times| times.split_whitespace().map(|time| time.parse().ok().map(Duration::from_secs_f64)).collect()),
+            variations: Vec::new(),
+            checksum: key("checksum").and_then(|checksum| u64::from_str_radix(checksum, 16).ok()),
+        })
+    }
+
+    /// The section of the archive, checksum included.
+    fn to_config(&self) -> String {
+        let text = self.fields();
+        format!("{}checksum = {:016x}\n", text, checksum(&text))
+    }
+
+    /// Whether the game is as it was saved, None if it was saved without a checksum.
+    pub fn is_intact(&self) -> Option<bool> {
+        self.checksum.map(|stored| stored == checksum(&self.fields()))
+    }
+
+    /// The section of the archive without the checksum, the text the checksum is taken over.
+    fn fields(&self) -> String {
+        let mut text = format!("[game.{}]\nx = {}\no = {}\n", self.id, self.players[0], self.players[1]);
+        if let Some(human) = self.human {
+            text.push_str(&format!("human = {}\n", human));
+        }
+        text.push_str(&format!("started = {}\nended = {}\n", self.started, self.ended));
+        if let Some(start) = &self.start {
+            text.push_str(&format!("start = {}\n", start));
+        }
+        let moves: Vec<String> = self.moves.iter().map(Coordinate::to_string).collect();
+        let winner = match self.winner {
+            Winner::Player(player) => player.to_string(),
+            Winner::Draw => "draw".to_string(),
+        };
+        text.push_str(&format!("moves = {}\nwinner = {}\ntermination = {}\n", moves.join(" "), winner, self.termination.name()));
+        if let Some(accuracy) = self.accuracy {
+            text.push_str(&format!("accuracy = {:.0}\n", accuracy));
+        }
+        if self.takebacks > 0 {
+            text.push_str(&format!("takebacks = {}\n", self.takebacks));
+        }
+        if let Some(difficulty) = self.difficulty {
+            text.push_str(&format!("difficulty = {}\n", difficulty.name()));
+        }
+        if !self.times.is_empty() {
+            let times: Vec<String> = self.times.iter().map(|time| time.map_or("-".to_string(), |time| format!("{:.1}", time.as_secs_f64()))).collect();
+            text.push_str(&format!("times = {}\n", times.join(" ")));
+        }
+        text
+    }
+
+    /// The board before the first move.
+    pub fn start_board(&self) -> Result<Board, ParseError> {
+        match &self.start {
+            Some(start) => Board::from_compact(start),
+            None => Ok(Board::new()),
+        }
+    }
+
+    /// The board after the last move, or an error if the saved moves can't be played.
+    pub fn final_board(&self) -> Result<Board, ParseError> {
+        let mut board = self.start_board()?;
+        for &coord in &self.moves {
+            if board.winner.is_some() || board.get_cell(coord).is_some() {
+                return Err(ParseError::new(&tr_args("error.archive_game", &[("id", &self.id)])));
+            }
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+            board.check_if_game_over();
+        }
+        if board.winner.is_none() {
+            board.finish(self.winner, self.termination);
+        }
+        Ok(board)
+    }
+
+    /// The result as seen by one of the players.
+    fn outcome_for(&self, player: Player) -> Outcome {
+        match self.winner {
+            Winner::Player(winner) if winner == player => Outcome::Win,
+            Winner::Player(_) => Outcome::Loss,
+            Winner::Draw => Outcome::Draw,
+        }
+    }
+
+    /// One line for `games list`, e.g. `#3  2026-10-15 14:00 UTC  Alice (O) vs perfect (X)  Alice wins, 7 moves`.
+    fn summary(&self) -> String {
+        let name = |player: Player| &self.players[player as usize];
+        let winner = match self.winner {
+            Winner::Player(player) => tr_args("winner.player", &[("player", name(player))]),
+            Winner::Draw => tr("winner.draw").to_string(),
+        };
+        tr_args(
+            "games.summary",
+            &[
+                ("id", &self.id),
+                ("date", &format_date(self.started)),
+                ("o", name(Player::O)),
+                ("x", name(Player::X)),
+                ("result", &winner),
+                ("moves", &self.moves.len()),
+            ],
+        )
+    }
+}
+
+/// What `games search` looks for. Empty fields match every game.
+#[derive(Default)]
+struct Filter {
+    player: Option<String>,
+    /// `win`, `draw` or `loss` for the `--player`, or the human if no player is given, or the winning mark.
+    result: Option<String>,
+    opening: Option<Coordinate>,
+}
+
+impl Filter {
+    fn matches(&self, game: &SavedGame) -> bool {
+        let side = match &self.player {
+            Some(player) => match [Player::X, Player::O].into_iter().find(|&side| game.players[side as usize].eq_ignore_ascii_case(player)) {
+                Some(side) => Some(side),
+                None => return false,
+            },
+            None => game.human,
+        };
+        let result = match self.result.as_deref() {
+            None => true,
+            Some("win") => side.is_some_and(|side| game.outcome_for(side) == Outcome::Win),
+            Some("loss") => side.is_some_and(|side| game.outcome_for(side) == Outcome::Loss),
+            Some("draw") => game.winner == Winner::Draw,
+            Some(mark) => parse_player(mark).is_some_and(|player| game.winner == Winner::Player(player)),
+        };
+        result && self.opening.is_none_or(|opening| game.moves.first() == Some(&opening))
+    }
+}
+
+fn path() -> PathBuf {
+    data_dir().join("games")
+}
+
+/// All saved games, oldest first.
+pub fn load() -> Result<Vec<SavedGame>, ParseError> {
+    let Ok(text) = fs::read_to_string(path()) else {
+        return Ok(Vec::new());
+    };
+    let config = Config::parse(&save_format::upgrade(&text, &path())?)?;
+    let mut games = config
+        .sections("game")
+        .iter()
+        .map(|id| SavedGame::from_config(id, &config).ok_or_else(|| ParseError::new(&tr_args("error.archive_game", &[("id", id)]))))
+        .collect::<Result<Vec<_>, _>>()?;
+    games.sort_by_key(|game| game.id);
+
+    // Named `<game>.<number>`
+    let mut variations: Vec<(u64, u64, Variation)> = Vec::new();
+    for name in config.sections("variation") {
+        let key = |field: &str| config.get(&format!("variation.{}.{}", name, field));
+        let variation = (|| {
+            let (id, number) = name.split_once('.')?;
+            let moves = key("moves")?.split_whitespace().map(|coord| parse_coordinates(coord.to_string()).ok()).collect::<Option<_>>()?;
+            Some((id.parse().ok()?, number.parse().ok()?, Variation { from: key("from")?.parse().ok()?, moves }))
+        })();
+        variations.push(variation.ok_or_else(|| ParseError::new(&tr_args("error.archive_variation", &[("name", &name)])))?);
+    }
+    variations.sort_by_key(|&(id, number, _)| (id, number));
+    for (id, _, variation) in variations {
+        if let Some(game) = games.iter_mut().find(|game| game.id == id) {
+            game.variations.push(variation);
+        }
+    }
+    Ok(games)
+}
+
+/// Adds a game to the end of the archive under the next free id, which is returned.
+pub fn save(mut game: SavedGame) -> Result<u64, GameError> {
+    game.id = load()?.last().map_or(1, |last| last.id + 1);
+    append(&game.to_config())?;
+    Ok(game.id)
+}
+
+/// Adds variations of a saved game to the end of the archive, numbered after those it already has.
+pub fn save_variations(game: &SavedGame, variations: &[Variation]) -> Result<(), GameError> {
+    let text: Vec<String> = variations
+        .iter()
+        .enumerate()
+        .map(|(number, variation)| {
+            format!(
+                "[variation.{}.{}]\nfrom = {}\nmoves = {}\n",
+                game.id,
+                game.variations.len() + number + 1,
+                variation.from,
+                variation.moves_text()
+            )
+        })
+        .collect();
+    if text.is_empty() { Ok(()) } else { Ok(append(&text.join("\n"))?) }
+}
+
+/// Adds the text to the end of the archive. An archive in an older format is first rewritten in the current
+/// one, so that the whole file is in the format of what is added.
+fn append(text: &str) -> Result<(), GameError> {
+    let path = path();
+    let error = || IoError::new(&tr_args("error.write_file", &[("path", &path.display())]));
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let upgraded = save_format::upgrade(&existing, &path)?;
+    if upgraded != existing {
+        path.parent().map(fs::create_dir_all).transpose().and_then(|_| fs::write(&path, upgraded)).map_err(|_| error())?;
+    }
+    OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", text))
+        .map_err(|_| error().into())
+}
+
+/// Explores a saved game, see `explore`, and keeps the variations tried.
+pub fn explore_game(game: &SavedGame, board: &Board) -> Result<(), GameError> {
+    let variations = explore::explore(board, &game.moves);
+    save_variations(game, &variations)?;
+    if !variations.is_empty() {
+        say(tr_args("explore.saved", &[("count", &variations.len()), ("id", &game.id)]));
+    }
+    Ok(())
+}
+
+/// The `games` subcommand.
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    match args.first().map(String::as_str) {
+        Some("list") if args.len() == 1 => print_games(&Filter::default()),
+        Some("search") => print_games(&parse_filter(&args[1..])?),
+        Some("show") if args.len() == 2 => show(&find(&args[1])?),
+        Some("verify") if args.len() == 1 => verify(),
+        Some("log") if args.len() == 2 => event_log::show(&args[1]),
+        Some("explore") if args.len() == 2 => {
+            let game = find(&args[1])?;
+            explore_game(&game, &game.start_board()?)
+        }
+        _ => Err(ParseError::new(tr("error.games_usage")).into()),
+    }
+}
+
+pub fn find(id: &str) -> Result<SavedGame, GameError> {
+    let id = parse_count(id)?;
+    let game = load()?.into_iter().find(|game| game.id == id);
+    Ok(game.ok_or_else(|| ParseError::new(&tr_args("error.no_saved_game", &[("id", &id)])))?)
+}
+
+fn parse_filter(args: &[String]) -> Result<Filter, GameError> {
+    let mut filter = Filter::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--player" => filter.player = Some(flag_value(arg, args.next())?.to_string()),
+            "--result" => {
+                let value = flag_value(arg, args.next())?.trim().to_lowercase();
+                if !["win", "draw", "loss", "x", "o"].contains(&value.as_str()) {
+                    return Err(ParseError::new(&tr_args("error.unknown_result", &[("value", &value)])).into());
+                }
+                filter.result = Some(value);
+            }
+            "--opening" => filter.opening = Some(parse_coordinates(flag_value(arg, args.next())?.to_string())?),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+    Ok(filter)
+}
+
+fn print_games(filter: &Filter) -> Result<(), GameError> {
+    let games: Vec<SavedGame> = load()?.into_iter().filter(|game| filter.matches(game)).collect();
+    if games.is_empty() {
+        println!("{}", tr("games.none"));
+    }
+    for game in games {
+        println!("{}", game.summary());
+    }
+    Ok(())
+}
+
+/// The summary, the moves in order and the final board.
+fn show(game: &SavedGame) -> Result<(), GameError> {
+    let board = game.final_board()?;
+    println!("{}", game.summary());
+    if let Some(start) = &game.start {
+        println!("{}", tr_args("games.start", &[("position", start)]));
+    }
+    let mut turn = game.start_board()?.turn;
+    for (number, coord) in game.moves.iter().enumerate() {
+        let time = game.times.get(number).copied().flatten().map_or(String::new(), |time| format!("  {:.1}s", time.as_secs_f64()));
+        println!("{:>3}. {} ({}) {}{}", number + 1, game.players[turn as usize], turn, coord, time);
+        turn = turn.opponent();
+    }
+    println!("{}", board);
+    let how = match game.termination {
+        Termination::Line => tr("games.by_line"),
+        Termination::Draw => tr("games.by_full_board"),
+        Termination::Agreement => tr("games.by_agreement"),
+        Termination::Resignation => tr("games.by_resignation"),
+        Termination::Timeout => tr("games.by_timeout"),
+    };
+    println!("{}", tr_args("games.ended", &[("how", &how), ("date", &format_date(game.ended))]));
+    for (number, variation) in game.variations.iter().enumerate() {
+        println!("{}", tr_args("explore.variation", &[("number", &(number + 1)), ("move", &variation.from), ("moves", &variation.moves_text())]));
+    }
+    if game.takebacks > 0 {
+        println!("{}", tr_args("games.takebacks", &[("count", &game.takebacks)]));
+    }
+    if let (Some(human), Some(accuracy)) = (game.human, game.accuracy) {
+        println!("{}", tr_args("games.accuracy", &[("player", &game.players[human as usize]), ("accuracy", &format!("{:.0}", accuracy))]));
+    }
+    Ok(())
+}
+
+/// Checks every game in the archive: that it is as it was saved and that its moves can be played and end in
+/// its result. An error if any game fails.
+fn verify() -> Result<(), GameError> {
+    let games = load()?;
+    let (mut unsigned, mut failed) = (0, 0);
+    for game in &games {
+        let problem = match (game.is_intact(), game.final_board()) {
+            (Some(false), _) => Some(tr("verify.edited")),
+            (_, Err(_)) => Some(tr("verify.unplayable")),
+            (_, Ok(board)) if board.winner != Some(game.winner) => Some(tr("verify.wrong_result")),
+            (None, Ok(_)) => {
+                unsigned += 1;
+                None
+            }
+            _ => None,
+        };
+        if let Some(problem) = problem {
+            println!("{}", tr_args("verify.failed_game", &[("id", &game.id), ("problem", &problem)]));
+            failed += 1;
+        }
+    }
+    let intact = games.len() - unsigned - failed;
+    println!("{}", tr_args("verify.summary", &[("games", &games.len()), ("intact", &intact), ("unsigned", &unsigned), ("failed", &failed)]));
+    if failed > 0 {
+        return Err(ParseError::new(&tr_args("error.archive_verify", &[("count", &failed)])).into());
+    }
+    Ok(())
+}
+
+/// 64-bit FNV-1a, the same on every platform and release, unlike the standard library's hasher.
+fn checksum(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+pub fn parse_player(text: &str) -> Option<Player> {
+    match text.trim().to_uppercase().as_str() {
+        "X" => Some(Player::X),
+        "O" => Some(Player::O),
+        _ => None,
+    }
+}
+
+pub fn seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+/// A Unix time as `YYYY-MM-DD HH:MM UTC`.
+pub fn format_date(seconds: u64) -> String {
+    // Civil date from days since 1970-01-01, counting in 400-year eras that start on March 1st
+    let (days, time) = (seconds / 86_400 + 719_468, seconds % 86_400);
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, time / 3_600, time % 3_600 / 60)
+}
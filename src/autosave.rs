@@ -0,0 +1,160 @@
+//! The game in progress, saved after every move and on `pause`, so that the next launch can offer to resume
+//! it if the game was paused or cut short by a crash or a closed terminal:
+//!
+//! ```text
+//! format = 1
+//! saved = 1760000000
+//! began = 1759999940
+//! human = X
+//! name.x = Alice
+//! takebacks = 0
+//! moves = 2-2 1-1
+//! arg.1 = --difficulty
+//! arg.2 = hard
+//! ```
+//!
+//! The game is set up again from the same arguments, so only the moves and what was asked at the start
+//! are kept. Clocks start over.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::archive::{format_date, parse_player, seconds};
+use crate::commands::is_yes;
+use crate::config::{data_dir, Config};
+use crate::error::IoError;
+use crate::events::{Event, Observer};
+use crate::i18n::{tr, tr_args};
+use crate::save_format;
+use crate::{get_input_from_console, parse_coordinates, say, Board, Coordinate, Player};
+
+/// What the game was started with, all it takes besides the moves to set it up again.
+pub struct Session {
+    /// The command line arguments.
+    pub args: Vec<String>,
+    /// The side of a lone human playing a bot, as picked or tossed for.
+    pub human: Option<Player>,
+    /// Names of X and O, as given or typed at the start.
+    pub names: [Option<String>; 2],
+    pub began: SystemTime,
+}
+
+/// A game left unfinished.
+pub struct Autosave {
+    pub session: Session,
+    pub moves: Vec<Coordinate>,
+    /// Move pairs taken back before the pause.
+    pub takebacks: usize,
+    /// Seconds since the Unix epoch.
+    pub saved: u64,
+}
+
+static SESSION: OnceLock<Session> = OnceLock::new();
+
+/// Sets the game that `save` writes the moves of. Can only be set once.
+pub fn start_session(session: Session) {
+    let _ = SESSION.set(session);
+}
+
+/// Whether a session was started, which a quick game doesn't.
+pub fn in_session() -> bool {
+    SESSION.get().is_some()
+}
+
+/// Saves the game on the board, replacing any saved before. The file is written next to the old one
+/// and then renamed over it, so a crash while saving leaves the old one whole.
+pub fn save(board: &Board) -> Result<(), IoError> {
+    let error = || IoError::new(tr("error.save_autosave"));
+    let session = SESSION.get().ok_or_else(error)?;
+    let moves: Vec<String> = board.history[..board.moves].iter().flatten().map(|&(_, coord)| coord.to_string()).collect();
+
+    let mut text = format!("{}saved = {}\nbegan = {}\n", save_format::header(), seconds(SystemTime::now()), seconds(session.began));
+    if let Some(human) = session.human {
+        text.push_str(&format!("human = {}\n", human));
+    }
+    for side in [Player::X, Player::O] {
+        if let Some(name) = &session.names[side as usize] {
+            text.push_str(&format!("name.{} = {}\n", side.to_string().to_lowercase(), name));
+        }
+    }
+    text.push_str(&format!("takebacks = {}\nmoves = {}\n", board.takebacks, moves.join(" ")));
+    for (number, arg) in session.args.iter().enumerate() {
+        text.push_str(&format!("arg.{} = {}\n", number + 1, arg));
+    }
+
+    let path = path();
+    let temporary = path.with_extension("tmp");
+    path.parent()
+        .map(fs::create_dir_all)
+        .transpose()
+        .and_then(|_| fs::write(&temporary, text))
+        .and_then(|_| fs::rename(&temporary, &path))
+        .map_err(|_| error())
+}
+
+/// The unfinished game, if there is one that can be read.
+pub fn load() -> Option<Autosave> {
+    let text = save_format::upgrade(&fs::read_to_string(path()).ok()?, &path()).ok()?;
+    let config = Config::parse(&text).ok()?;
+    let number = |key: &str| config.get(key).and_then(|value| value.parse::<u64>().ok());
+    let moves = match config.get("moves") {
+        Some(moves) => moves.split_whitespace().map(|coord| parse_coordinates(coord.to_string()).ok()).collect::<Option<_>>()?,
+        None => Vec::new(),
+    };
+    let name = |side: Player| config.get(&format!("name.{}", side.to_string().to_lowercase())).map(str::to_string);
+    let args = (1..).map_while(|number| config.get(&format!("arg.{}", number)).map(str::to_string)).collect();
+    Some(Autosave {
+        session: Session {
+            args,
+            human: config.get("human").and_then(parse_player),
+            names: [name(Player::X), name(Player::O)],
+            began: UNIX_EPOCH + Duration::from_secs(number("began")?),
+        },
+        moves,
+        takebacks: number("takebacks").unwrap_or(0) as usize,
+        saved: number("saved")?,
+    })
+}
+
+/// Keeps the saved game up to date as moves are played and taken back, and removes it when the game ends.
+pub struct Saver;
+
+impl Observer for Saver {
+    fn notify(&mut self, event: &Event, board: &Board) {
+        match event {
+            Event::MovePlayed { .. } | Event::UndoPerformed { .. } if board.winner.is_none() => {
+                if let Err(e) = save(board) {
+                    say(tr_args("error.prefix", &[("message", &e)]));
+                }
+            }
+            Event::GameOver(_) => discard(),
+            _ => {}
+        }
+    }
+}
+
+/// Removes the unfinished game, if there is one.
+pub fn discard() {
+    let _ = fs::remove_file(path());
+}
+
+/// Asks whether to resume the unfinished game, if there is one. Either way it is taken out of the data
+/// directory, to be saved again once the resumed game is set up.
+pub fn offer_resume() -> Option<Autosave> {
+    let autosave = load()?;
+    discard();
+    let moves = autosave.moves.len();
+    println!("{}", tr_args("prompt.resume", &[("date", &format_date(autosave.saved)), ("moves", &moves)]));
+    if get_input_from_console().is_ok_and(|answer| is_yes(&answer)) {
+        Some(autosave)
+    } else {
+        println!("{}", tr("autosave.discarded"));
+        None
+    }
+}
+
+fn path() -> PathBuf {
+    data_dir().join("autosave")
+}
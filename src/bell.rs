@@ -0,0 +1,35 @@
+//! Sound cues, rung on the terminal bell when `--bell` is on: when it is a human's turn, when what they
+//! typed can't be played, and when the game is won. Handy with the window in the background while the
+//! other side takes its time.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::events::{Event, Observer};
+use crate::{Board, Winner};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns the cues on or off for the rest of the run.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Rings the bell, if the cues are on.
+pub fn ring() {
+    if ENABLED.load(Ordering::Relaxed) {
+        print!("\x07");
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Rings when the game is won.
+pub struct Bell;
+
+impl Observer for Bell {
+    fn notify(&mut self, event: &Event, _: &Board) {
+        if let Event::GameOver(Winner::Player(_)) = event {
+            ring();
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{Board, Coordinate};
+
+const CENTER: usize = 4;
+const CORNER: usize = 0;
+
+/// Known best answers for the first two plies, so bots using the book don't search the opening.
+/// The first move takes a corner. The reply takes the center, or a corner if the center is gone.
+pub fn book_move(board: &Board) -> Option<Coordinate> {
+    match board.cells.iter().filter(|cell| cell.is_some()).count() {
+        0 => Coordinate::from_index(CORNER),
+        1 if board.cells[CENTER].is_none() => Coordinate::from_index(CENTER),
+        1 => Coordinate::from_index(CORNER),
+        _ => None,
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, Instant};
+
+use crate::Player;
+
+/// Chess-style clocks. Each player can have a time bank for the whole game, a limit per move, or both.
+pub struct Clock {
+    bank: Option<[Duration; 2]>,
+    per_move: Option<Duration>,
+    /// In casual mode running out of time plays a random move instead of losing.
+    pub casual: bool,
+    running: Option<(Player, Instant)>,
+}
+
+impl Clock {
+    pub fn new(bank: Option<Duration>, per_move: Option<Duration>, casual: bool) -> Clock {
+        Clock { bank: bank.map(|bank| [bank; 2]), per_move, casual, running: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.bank.is_some() || self.per_move.is_some()
+    }
+
+    pub fn start(&mut self, player: Player) {
+        self.running = Some((player, Instant::now()));
+    }
+
+    /// Stops the running clock and charges the elapsed time to its player's bank.
+    pub fn stop(&mut self) {
+        if let Some((player, started)) = self.running.take() {
+            if let Some(bank) = self.bank.as_mut() {
+                let remaining = &mut bank[player as usize];
+                *remaining = remaining.saturating_sub(started.elapsed());
+            }
+        }
+    }
+
+    /// Time left for the player, counting the move in progress.
+    pub fn remaining(&self, player: Player) -> Option<Duration> {
+        let bank = self.bank.map(|bank| bank[player as usize]);
+        match self.running {
+            Some((running, started)) if running == player => {
+                let elapsed = started.elapsed();
+                let bank = bank.map(|bank| bank.saturating_sub(elapsed));
+                let this_move = self.per_move.map(|limit| limit.saturating_sub(elapsed));
+                match (bank, this_move) {
+                    (Some(bank), Some(this_move)) => Some(bank.min(this_move)),
+                    (bank, this_move) => bank.or(this_move),
+                }
+            }
+            _ => match (bank, self.per_move) {
+                (Some(bank), Some(limit)) => Some(bank.min(limit)),
+                (bank, limit) => bank.or(limit),
+            },
+        }
+    }
+
+    /// The instant at which the running player flags, if any limit applies.
+    pub fn deadline(&self) -> Option<Instant> {
+        let (player, _) = self.running?;
+        self.remaining(player).map(|remaining| Instant::now() + remaining)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        match self.running {
+            Some((player, _)) => self.remaining(player) == Some(Duration::ZERO),
+            None => false,
+        }
+    }
+}
+
+impl Display for Clock {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let format_time = |player: Player| match self.remaining(player) {
+            Some(time) => {
+                // Round up, so a clock only shows 0:00 once it has actually run out
+                let seconds = time.as_millis().div_ceil(1000);
+                format!("{}:{:02}", seconds / 60, seconds % 60)
+            }
+            None => "-:--".to_string(),
+        };
+        write!(f, "{} {}  |  {} {}", Player::X.name(), format_time(Player::X), Player::O.name(), format_time(Player::O))
+    }
+}
@@ -0,0 +1,274 @@
+use crate::analysis;
+use crate::autosave;
+use crate::error::{GameError, MoveError, ParseError};
+use crate::game_result::Termination;
+use crate::i18n::{tr, tr_args};
+use crate::render;
+use crate::report;
+use crate::svg;
+use crate::{bot_accepts_draw, get_input_from_console, parse_coordinates, say, Board, Coordinate, Winner};
+
+/// A parsed line of player input.
+#[derive(PartialEq, Clone)]
+pub enum Command {
+    Select(Coordinate),
+    Confirm,
+    Help,
+    Resign,
+    OfferDraw,
+    Explain,
+    Undo,
+    Redo,
+    /// Writes the position to an SVG file, `position.svg` unless a file is given.
+    ExportSvg(Option<String>),
+    /// Prints what it takes to set the game up again for a bug report, or writes it to the file given.
+    Report(Option<String>),
+    /// Shows the best moves, with `hint map` also the result of every empty cell on the board.
+    Hint { map: bool },
+    /// Saves the game to be resumed on the next launch, and quits.
+    Pause,
+}
+
+impl Command {
+    pub fn parse(input: &str) -> Result<Command, ParseError> {
+        // The commands that take an argument
+        let (name, argument) = input.trim().split_once(char::is_whitespace).unwrap_or((input.trim(), ""));
+        let argument = argument.trim();
+        match find(name).map(|command| command.name) {
+            Some("export-svg") => return Ok(Command::ExportSvg((!argument.is_empty()).then(|| argument.to_string()))),
+            Some("report") => return Ok(Command::Report((!argument.is_empty()).then(|| argument.to_string()))),
+            Some("hint") if argument.is_empty() => return Ok(Command::Hint { map: false }),
+            Some("hint") if argument.eq_ignore_ascii_case("map") => return Ok(Command::Hint { map: true }),
+            _ => {}
+        }
+
+        match find(input).map(|command| command.name) {
+            Some("ok") => Ok(Command::Confirm),
+            Some("help") => Ok(Command::Help),
+            Some("resign") => Ok(Command::Resign),
+            Some("draw") => Ok(Command::OfferDraw),
+            Some("explain") => Ok(Command::Explain),
+            Some("undo") => Ok(Command::Undo),
+            Some("redo") => Ok(Command::Redo),
+            Some("pause") => Ok(Command::Pause),
+            // A bad row or column is worth pointing at, anything else may not have been meant as a move at all
+            _ => parse_coordinates(input.to_string()).map(Command::Select).map_err(|e| match (e.span(), suggest(name)) {
+                (Some(_), _) => e,
+                (None, Some(command)) => ParseError::new(&tr_args("error.did_you_mean", &[("input", &name), ("command", &command)])),
+                (None, None) => ParseError::new(tr("error.invalid_input")),
+            }),
+        }
+    }
+}
+
+/// An in-game command as shown by `help`.
+pub struct CommandInfo {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    /// i18n key of the one-line description.
+    pub description: &'static str,
+}
+
+/// Every command the player can type during their turn. `help` is generated from this list,
+/// so a new command only needs an entry here to be documented.
+pub const COMMANDS: &[CommandInfo] = &[
+    CommandInfo { name: "row-col", aliases: &[], description: "help.select" },
+    CommandInfo { name: "ok", aliases: &[], description: "help.ok" },
+    CommandInfo { name: "explain", aliases: &[], description: "help.explain" },
+    CommandInfo { name: "hint", aliases: &[], description: "help.hint" },
+    CommandInfo { name: "undo", aliases: &[], description: "help.undo" },
+    CommandInfo { name: "redo", aliases: &[], description: "help.redo" },
+    CommandInfo { name: "export-svg", aliases: &[], description: "help.export_svg" },
+    CommandInfo { name: "report", aliases: &[], description: "help.report" },
+    CommandInfo { name: "pause", aliases: &[], description: "help.pause" },
+    CommandInfo { name: "resign", aliases: &[], description: "help.resign" },
+    CommandInfo { name: "draw", aliases: &[], description: "help.draw" },
+    CommandInfo { name: "help", aliases: &["?"], description: "help.help" },
+];
+
+pub fn find(input: &str) -> Option<&'static CommandInfo> {
+    let input = input.trim().to_lowercase();
+    COMMANDS
+        .iter()
+        .find(|command| command.name == input || command.aliases.contains(&input.as_str()))
+}
+
+/// The command closest to a word that isn't one, if it is only a typo or two away: one edit for words of two
+/// or three letters, two for longer ones. A single character is too little to go on.
+fn suggest(word: &str) -> Option<&'static str> {
+    let word = &word.trim().to_lowercase();
+    let allowed = match word.chars().count() {
+        0..=1 => return None,
+        2..=3 => 1,
+        _ => 2,
+    };
+    COMMANDS
+        .iter()
+        .filter(|command| command.name != "row-col")
+        .flat_map(|command| std::iter::once(&command.name).chain(command.aliases).map(move |name| (command.name, edit_distance(word, name))))
+        .filter(|&(_, distance)| distance > 0 && distance <= allowed)
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(name, _)| name)
+}
+
+/// How many characters have to be inserted, removed or replaced to turn one word into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b) in b.iter().enumerate() {
+            current.push((previous[j] + usize::from(a != b)).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+pub fn help_text() -> String {
+    let mut text = format!("{}\n", tr("help.header"));
+    for command in COMMANDS {
+        let mut names = command.name.to_string();
+        for alias in command.aliases {
+            names.push_str(", ");
+            names.push_str(alias);
+        }
+        text.push_str(&format!("  {:<10} {}\n", names, tr(command.description)));
+    }
+    text
+}
+
+/// What became of the player's turn after a command.
+#[derive(PartialEq, Clone, Copy)]
+pub enum Outcome {
+    /// It is still their turn.
+    Waiting,
+    /// Their move was played, or the game decided, and the turn is over.
+    Played,
+    /// They want to save the game and quit, which is up to the game loop.
+    Paused,
+}
+
+/// Applies a command to the board. A draw offer goes to the bot, or to the other player when two humans share
+/// the keyboard.
+pub fn execute(board: &mut Board, command: Command, opponent_is_human: bool) -> Result<Outcome, GameError> {
+    match command {
+        Command::Select(coordinates) => {
+            // Only empty cells can be highlighted
+            if board.get_cell(coordinates).is_some() {
+                return Err(MoveError::new(tr("error.cell_taken")).into());
+            }
+            board.highlight(coordinates);
+            say(&board);
+            Ok(Outcome::Waiting)
+        }
+        Command::Confirm => {
+            let highlight = board.highlight.ok_or(MoveError::new(tr("error.no_selection")))?;
+            board.play_move(highlight);
+            board.highlight = None;
+            Ok(Outcome::Played)
+        }
+        Command::Help => {
+            say(help_text().trim_end());
+            Ok(Outcome::Waiting)
+        }
+        Command::Explain => {
+            say(analysis::explain(board).trim_end());
+            Ok(Outcome::Waiting)
+        }
+        Command::Hint { map } => {
+            let values = analysis::move_values(board);
+            if map {
+                say(render::heat_map(board, &values).trim_end());
+            }
+            say(analysis::hint(&values));
+            Ok(Outcome::Waiting)
+        }
+        // Both take a whole move pair, so it is the same player's turn afterwards
+        // Against another human, the opponent has to agree to it
+        Command::Undo => {
+            if board.moves < 2 {
+                return Err(MoveError::new(tr("error.nothing_to_undo")).into());
+            }
+            if opponent_is_human {
+                let opponent = board.turn.opponent().name();
+                if !opponent_agrees(board, "prompt.accept_takeback")? {
+                    say(tr_args("takeback.declined", &[("player", &opponent)]));
+                    return Ok(Outcome::Waiting);
+                }
+                say(tr_args("takeback.accepted", &[("player", &opponent)]));
+            }
+            board.undo();
+            board.undo();
+            board.takebacks += 1;
+            say(&board);
+            Ok(Outcome::Waiting)
+        }
+        Command::Redo => {
+            if board.redoable() < 2 {
+                return Err(MoveError::new(tr("error.nothing_to_redo")).into());
+            }
+            board.redo();
+            board.redo();
+            say(&board);
+            Ok(Outcome::Waiting)
+        }
+        Command::ExportSvg(path) => {
+            let path = path.unwrap_or_else(|| "position.svg".to_string());
+            svg::write(board, &path)?;
+            say(tr_args("svg.written", &[("path", &path)]));
+            Ok(Outcome::Waiting)
+        }
+        Command::Report(None) => {
+            say(report::bundle(board).trim_end());
+            Ok(Outcome::Waiting)
+        }
+        Command::Report(Some(path)) => {
+            report::write(board, &path)?;
+            say(tr_args("report.written", &[("path", &path)]));
+            Ok(Outcome::Waiting)
+        }
+        // A quick game keeps nothing, so there is nothing to resume it from
+        Command::Pause if !autosave::in_session() => Err(MoveError::new(tr("error.pause_quick")).into()),
+        Command::Pause => Ok(Outcome::Paused),
+        Command::Resign => {
+            say(tr_args("result.resigned", &[("player", &board.turn.name())]));
+            board.finish(Winner::Player(board.turn.opponent()), Termination::Resignation);
+            Ok(Outcome::Played)
+        }
+        Command::OfferDraw if opponent_is_human => {
+            let opponent = board.turn.opponent().name();
+            if opponent_agrees(board, "prompt.accept_draw")? {
+                say(tr_args("draw.accepted", &[("player", &opponent)]));
+                board.finish(Winner::Draw, Termination::Agreement);
+                Ok(Outcome::Played)
+            } else {
+                say(tr_args("draw.declined", &[("player", &opponent)]));
+                Ok(Outcome::Waiting)
+            }
+        }
+        Command::OfferDraw => {
+            if bot_accepts_draw(board, board.turn.opponent()) {
+                say(tr("bot.accepts_draw"));
+                board.finish(Winner::Draw, Termination::Agreement);
+                Ok(Outcome::Played)
+            } else {
+                say(tr("bot.declines_draw"));
+                Ok(Outcome::Waiting)
+            }
+        }
+    }
+}
+
+/// Asks the other player at the keyboard to agree to what the player to move proposes.
+fn opponent_agrees(board: &Board, prompt: &'static str) -> Result<bool, GameError> {
+    let (player, opponent) = (board.turn.name(), board.turn.opponent().name());
+    say(tr_args(prompt, &[("player", &player), ("opponent", &opponent)]));
+    Ok(is_yes(&get_input_from_console()?))
+}
+
+/// Whether the answer to a yes/no question is yes, in the current language.
+pub fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim().to_lowercase();
+    tr("prompt.yes_answers").split(',').any(|yes| yes.trim() == answer)
+}
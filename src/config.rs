@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::ParseError;
+use crate::i18n::tr_args;
+
+/// Directory holding the config file and anything else we persist between runs.
+/// `TICTACTOE_HOME` overrides the default of `~/.tictactoe`.
+pub fn data_dir() -> PathBuf {
+    if let Ok(dir) = env::var("TICTACTOE_HOME") {
+        return PathBuf::from(dir);
+    }
+    match env::var("HOME").or_else(|_| env::var("USERPROFILE")) {
+        Ok(home) => PathBuf::from(home).join(".tictactoe"),
+        Err(_) => PathBuf::from(".tictactoe"),
+    }
+}
+
+/// Turns a player name into something safe to use as a file name, whatever they typed.
+pub fn safe_file_name(name: &str) -> String {
+    name.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+/// Flat `key = value` settings. Keys under a `[section]` header are stored as `section.key`.
+#[derive(Debug, Default)]
+pub struct Config {
+    values: HashMap<String, String>,
+}
+
+impl Config {
+    /// Loads the config file, treating a missing file as an empty config.
+    pub fn load() -> Result<Config, ParseError> {
+        let path = data_dir().join("config");
+        match fs::read_to_string(&path) {
+            Ok(text) => Config::parse(&text),
+            Err(_) => Ok(Config::default()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Config, ParseError> {
+        let mut values = HashMap::new();
+        let mut section = String::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                section = format!("{}.", name.trim());
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::new(&tr_args("error.config_line", &[("line", &(number + 1))])))?;
+            values.insert(format!("{}{}", section, key.trim()), value.trim().to_string());
+        }
+
+        Ok(Config { values })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|value| value.as_str())
+    }
+
+    /// Sets a key, over any value read from the file.
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    /// Every key with its value, sorted by key.
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        let mut entries: Vec<(&str, &str)> = self.values.iter().map(|(key, value)| (key.as_str(), value.as_str())).collect();
+        entries.sort();
+        entries
+    }
+
+    /// Whether the file has a `[section]` with at least one key in it.
+    pub fn has_section(&self, section: &str) -> bool {
+        let prefix = format!("{}.", section);
+        self.values.keys().any(|key| key.starts_with(&prefix))
+    }
+
+    /// The names of the `[<prefix>.<name>]` sections, sorted, e.g. the agents for `agent`.
+    pub fn sections(&self, prefix: &str) -> Vec<String> {
+        let prefix = format!("{}.", prefix);
+        let mut names: Vec<String> = self
+            .values
+            .keys()
+            .filter_map(|key| key.strip_prefix(&prefix)?.rsplit_once('.').map(|(name, _)| name.to_string()))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
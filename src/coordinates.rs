@@ -0,0 +1,244 @@
+//! Reading the moves players type. Besides `row-col`, a move can be written with a space or a comma
+//! between the numbers, as two digits, as a column letter and a row, or as one digit for the cell:
+//!
+//! ```text
+//! 1-2   1 2   1,2   12   b1   1b
+//! ```
+//!
+//! Single digits number the cells in reading order by default, or like a numeric keypad with
+//! `--digits numpad`. `--digits off` turns them off.
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::ParseError;
+use crate::i18n::tr;
+use crate::{Coordinate, Index};
+
+/// How a single digit 1-9 names a cell.
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Default)]
+pub enum DigitLayout {
+    /// 1 2 3 on the top row, 7 8 9 on the bottom one.
+    #[default]
+    Reading,
+    /// 7 8 9 on the top row, 1 2 3 on the bottom one.
+    Numpad,
+    /// Single digits are not moves.
+    Off,
+}
+
+impl DigitLayout {
+    pub const ALL: [DigitLayout; 3] = [DigitLayout::Reading, DigitLayout::Numpad, DigitLayout::Off];
+
+    pub fn from_name(name: &str) -> Option<DigitLayout> {
+        DigitLayout::ALL.iter().copied().find(|layout| layout.name() == name.trim().to_lowercase())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DigitLayout::Reading => "reading",
+            DigitLayout::Numpad => "numpad",
+            DigitLayout::Off => "off",
+        }
+    }
+
+    /// The digit that names a cell, 0-8 row by row. None when single digits are off.
+    pub fn digit(&self, cell: usize) -> Option<usize> {
+        (1..=9).find(|&digit| self.cell(digit) == Some(cell))
+    }
+
+    /// The cell a digit names, 0-8 row by row.
+    fn cell(&self, digit: usize) -> Option<usize> {
+        match (self, digit) {
+            (_, 0) | (_, 10..) | (DigitLayout::Off, _) => None,
+            (DigitLayout::Reading, digit) => Some(digit - 1),
+            (DigitLayout::Numpad, digit) => Some((9 - digit) / 3 * 3 + (digit - 1) % 3),
+        }
+    }
+}
+
+static LAYOUT: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the digit layout for the moves players type from now on.
+pub fn set_digit_layout(layout: DigitLayout) {
+    LAYOUT.store(layout as usize, Ordering::Relaxed);
+}
+
+pub fn digit_layout() -> DigitLayout {
+    DigitLayout::ALL[LAYOUT.load(Ordering::Relaxed)]
+}
+
+/// A piece of a typed move, with the bytes of the input it came from.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Token {
+    /// A run of digits, kept as typed so that `12` can be split into a row and a column.
+    Number(String, Range<usize>),
+    /// A run of letters.
+    Word(String, Range<usize>),
+    /// A `-`, a `,` or whitespace between the other tokens.
+    Separator(Range<usize>),
+}
+
+/// A row or a column counted from 1, with the bytes of the input it came from.
+pub type Numbered = (usize, Range<usize>);
+
+/// Splits the input into numbers, words and separators. Anything else is an error pointing at it.
+pub fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens: Vec<Token> = Vec::new();
+    for (start, c) in input.char_indices() {
+        let span = start..start + c.len_utf8();
+        match (tokens.last_mut(), c) {
+            (Some(Token::Number(text, run)), c) if c.is_ascii_digit() => {
+                text.push(c);
+                run.end = span.end;
+            }
+            (Some(Token::Word(text, run)), c) if c.is_alphabetic() => {
+                text.push(c);
+                run.end = span.end;
+            }
+            (Some(Token::Separator(run)), c) if is_separator(c) => run.end = span.end,
+            (_, c) if c.is_ascii_digit() => tokens.push(Token::Number(c.to_string(), span)),
+            (_, c) if c.is_alphabetic() => tokens.push(Token::Word(c.to_string(), span)),
+            (_, c) if is_separator(c) => tokens.push(Token::Separator(span)),
+            _ => return Err(ParseError::new(tr("error.format")).at(span)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_separator(c: char) -> bool {
+    c == '-' || c == ',' || c.is_whitespace()
+}
+
+/// Reads a move from its tokens, with single digits read in the given layout. Errors about the row or
+/// the column point at it.
+pub fn parse_tokens(tokens: &[Token], layout: DigitLayout) -> Result<Coordinate, ParseError> {
+    let (row, col) = row_and_column(tokens, layout)?;
+    coordinate(row, col)
+}
+
+/// The row and the column the tokens name, counted from 1 and each with the bytes of the input it came from,
+/// whether or not they are on the board.
+pub fn row_and_column(tokens: &[Token], layout: DigitLayout) -> Result<(Numbered, Numbered), ParseError> {
+    let format_error = || ParseError::new(tr("error.format"));
+    let parts: Vec<&Token> = tokens.iter().filter(|token| !matches!(token, Token::Separator(_))).collect();
+    match parts[..] {
+        [Token::Number(digit, span)] if digit.len() == 1 => {
+            let cell = layout.cell(number(digit)).ok_or_else(|| format_error().at(span.clone()))?;
+            Ok(((cell / 3 + 1, span.clone()), (cell % 3 + 1, span.clone())))
+        }
+        // Two digits written together, row then column
+        [Token::Number(digits, span)] if digits.len() == 2 => {
+            let (row, col) = digits.split_at(1);
+            Ok(((number(row), span.start..span.start + 1), (number(col), span.start + 1..span.end)))
+        }
+        [Token::Number(row, row_span), Token::Number(col, col_span)] => Ok(((number(row), row_span.clone()), (number(col), col_span.clone()))),
+        // A column letter and a row, in either order
+        [Token::Word(letter, col_span), Token::Number(row, row_span)] | [Token::Number(row, row_span), Token::Word(letter, col_span)]
+            if letter.chars().count() == 1 =>
+        {
+            Ok(((number(row), row_span.clone()), (column_number(letter), col_span.clone())))
+        }
+        _ => Err(format_error()),
+    }
+}
+
+/// Reads the move typed, trimmed. Spans in its errors are into the trimmed input.
+pub fn parse(input: &str, layout: DigitLayout) -> Result<Coordinate, ParseError> {
+    let input = input.trim();
+    match tokenize(input) {
+        Ok(tokens) => parse_tokens(&tokens, layout),
+        // Without a number in it the input was hardly meant as a move, so there is nothing to point at
+        Err(_) if !input.contains(|c: char| c.is_ascii_digit()) => Err(ParseError::new(tr("error.format"))),
+        Err(e) => Err(e),
+    }
+}
+
+/// A run of digits as a number, too large for the board if it doesn't fit in a usize.
+fn number(digits: &str) -> usize {
+    digits.parse().unwrap_or(usize::MAX)
+}
+
+/// The column a letter stands for, counting from 1 at `a`, and 0 for anything that isn't a letter of
+/// the alphabet so that it is out of range.
+fn column_number(letter: &str) -> usize {
+    match letter.to_ascii_lowercase().as_bytes() {
+        [c @ b'a'..=b'z'] => (c - b'a') as usize + 1,
+        _ => 0,
+    }
+}
+
+/// The cell at a row and column counted from 1, or an error pointing at whichever is off the board.
+fn coordinate((row, row_span): Numbered, (col, col_span): Numbered) -> Result<Coordinate, ParseError> {
+    // Counting from 1, so 0 wraps around and is out of range like any other bad number
+    let row = Index::try_from(row.wrapping_sub(1)).map_err(|_| ParseError::new(tr("error.row_range")).at(row_span))?;
+    let col = Index::try_from(col.wrapping_sub(1)).map_err(|_| ParseError::new(tr("error.col_range")).at(col_span))?;
+    Ok(Coordinate { row, col })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, row_and_column, tokenize, DigitLayout, Token};
+
+    #[test]
+    fn every_form_reads_the_same_cell() {
+        let cases = [
+            ("1-2", DigitLayout::Reading, "1-2"),
+            ("1 2", DigitLayout::Reading, "1-2"),
+            ("1,2", DigitLayout::Reading, "1-2"),
+            ("12", DigitLayout::Reading, "1-2"),
+            ("b1", DigitLayout::Reading, "1-2"),
+            ("1b", DigitLayout::Reading, "1-2"),
+            ("B1", DigitLayout::Off, "1-2"),
+            (" 3 - 1 ", DigitLayout::Off, "3-1"),
+            ("1", DigitLayout::Reading, "1-1"),
+            ("5", DigitLayout::Reading, "2-2"),
+            ("7", DigitLayout::Reading, "3-1"),
+            ("1", DigitLayout::Numpad, "3-1"),
+            ("5", DigitLayout::Numpad, "2-2"),
+            ("9", DigitLayout::Numpad, "1-3"),
+        ];
+        for (input, layout, expected) in cases {
+            let coord = parse(input, layout).unwrap_or_else(|e| panic!("{:?} in {:?}: {}", input, layout, e));
+            assert_eq!(coord.to_string(), expected, "{:?} in {:?}", input, layout);
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let cases = [
+            ("4-2", DigitLayout::Reading, Some(0..1)),
+            ("1-4", DigitLayout::Reading, Some(2..3)),
+            ("0 1", DigitLayout::Reading, Some(0..1)),
+            ("14", DigitLayout::Reading, Some(1..2)),
+            ("d1", DigitLayout::Reading, Some(0..1)),
+            ("1?2", DigitLayout::Reading, Some(1..2)),
+            ("0", DigitLayout::Reading, Some(0..1)),
+            ("5", DigitLayout::Off, Some(0..1)),
+            ("", DigitLayout::Reading, None),
+            ("1-2-3", DigitLayout::Reading, None),
+            ("bb1", DigitLayout::Reading, None),
+            ("help?", DigitLayout::Reading, None),
+        ];
+        for (input, layout, span) in cases {
+            match parse(input, layout) {
+                Ok(coord) => panic!("{:?} in {:?} read as {}", input, layout, coord),
+                Err(e) => assert_eq!(e.span(), span, "{:?} in {:?}", input, layout),
+            }
+        }
+    }
+
+    #[test]
+    fn tokens_keep_their_bytes() {
+        let tokens = tokenize("12 b").unwrap_or_default();
+        assert_eq!(tokens, [Token::Number("12".to_string(), 0..2), Token::Separator(2..3), Token::Word("b".to_string(), 3..4)]);
+        assert_eq!(tokenize("1?").map_err(|e| e.span()), Err(Some(1..2)));
+    }
+
+    #[test]
+    fn rows_and_columns_off_the_board_are_kept() {
+        let tokens = tokenize("4-12").unwrap_or_default();
+        let numbers = row_and_column(&tokens, DigitLayout::Reading).map_err(|e| e.to_string());
+        assert_eq!(numbers, Ok(((4, 0..1), (12, 2..4))));
+    }
+}
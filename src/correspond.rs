@@ -0,0 +1,130 @@
+//! Games played by correspondence, one move per run, through a game file passed back and forth by email
+//! or chat:
+//!
+//! ```text
+//! correspond <file> [<move>]
+//! ```
+//!
+//! The file is created with the first move. Each run checks the moves in it, plays one more, typed after
+//! the file name or asked for, and writes it back. The moves are printed in one line to paste along.
+//!
+//! ```text
+//! # Tic-tac-toe by correspondence: correspond <this file> <move>
+//! format = 1
+//! moves = 2-2 1-1 3-3
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{GameError, IoError, MoveError, ParseError};
+use crate::game_result::GameResult;
+use crate::generate::play;
+use crate::i18n::{tr, tr_args};
+use crate::save_format;
+use crate::{get_input_from_console, parse_coordinates, Board};
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let (path, typed) = match args {
+        [path] => (path, None),
+        [path, typed] => (path, Some(typed.clone())),
+        [] => return Err(ParseError::new(tr("error.correspond_file")).into()),
+        [_, _, arg, ..] => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+    };
+
+    let mut board = load(path)?;
+    if let Some(result) = GameResult::new(&board, Default::default(), Default::default()) {
+        println!("{}", board);
+        println!("{}", result.headline());
+        return Ok(());
+    }
+
+    let input = match typed {
+        Some(input) => input,
+        None => {
+            println!("{}", board);
+            println!("{}", tr_args("prompt.your_turn", &[("player", &board.turn.label())]));
+            get_input_from_console()?
+        }
+    };
+    let coord = parse_coordinates(input)?;
+    if board.get_cell(coord).is_some() {
+        return Err(MoveError::new(tr("error.cell_taken")).into());
+    }
+    play(&mut board, coord);
+    save(path, &board)?;
+
+    println!("{}", board);
+    println!("{}", tr_args("correspond.moves", &[("moves", &moves(&board))]));
+    match GameResult::new(&board, Default::default(), Default::default()) {
+        Some(result) => println!("{}", result.headline()),
+        None => println!("{}", tr_args("correspond.next", &[("player", &board.turn.name()), ("path", &path)])),
+    }
+    Ok(())
+}
+
+/// The game in the file, a new one if there is no file yet. Moves that couldn't have been played are
+/// an error, so a file edited by hand can't put the game somewhere it never was.
+fn load(path: &str) -> Result<Board, GameError> {
+    let mut board = Board::new();
+    if !Path::new(path).exists() {
+        return Ok(board);
+    }
+    let text = fs::read_to_string(path).map_err(|_| IoError::new(&tr_args("error.read_file", &[("path", &path)])))?;
+    let invalid = || ParseError::new(&tr_args("error.correspond_moves", &[("path", &path)]));
+    let config = Config::parse(&save_format::upgrade(&text, Path::new(path))?)?;
+    for coord in config.get("moves").unwrap_or_default().split_whitespace() {
+        let coord = parse_coordinates(coord.to_string()).map_err(|_| invalid())?;
+        if board.winner.is_some() || board.get_cell(coord).is_some() {
+            return Err(invalid().into());
+        }
+        play(&mut board, coord);
+    }
+    Ok(board)
+}
+
+fn save(path: &str, board: &Board) -> Result<(), IoError> {
+    let text = format!("{}{}\nmoves = {}\n", save_format::header(), tr("correspond.header"), moves(board));
+    fs::write(path, text).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &path)])))
+}
+
+/// The moves played so far, as they are written in the file.
+fn moves(board: &Board) -> String {
+    let moves: Vec<String> = board.history[..board.moves].iter().flatten().map(|&(_, coord)| coord.to_string()).collect();
+    moves.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::{load, moves, save};
+    use crate::generate::play;
+    use crate::save_format;
+    use crate::{parse_coordinates, Board};
+
+    #[test]
+    fn saved_games_upgrade_and_load_again() {
+        let path = std::env::temp_dir().join(format!("correspond-{}.txt", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let mut board = Board::new();
+        for coord in ["2-2", "1-1", "3-3"] {
+            play(&mut board, parse_coordinates(coord.to_string()).unwrap());
+        }
+        save(&path, &board).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(save_format::upgrade(&text, Path::new(&path)).unwrap(), text);
+        assert_eq!(moves(&load(&path).unwrap()), moves(&board));
+
+        // A version from the future is refused, wherever the format line is
+        let future = text.replace("format = 1", "format = 99");
+        assert!(save_format::upgrade(&future, Path::new(&path)).is_err());
+        let commented = format!("# moved below a comment\n\n{}", future);
+        assert!(save_format::upgrade(&commented, Path::new(&path)).is_err());
+        fs::write(&path, &future).unwrap();
+        assert!(load(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt::{self, Display, Formatter};
+
+/// How strong the bot plays. Below `Perfect` the bot sometimes blunders into a random move.
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    #[default]
+    Perfect,
+    /// Follows the blunder rate stored in the player's profile, which moves with their results.
+    Adaptive,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 5] =
+        [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard, Difficulty::Perfect, Difficulty::Adaptive];
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL.iter().copied().find(|difficulty| difficulty.name() == name.trim().to_lowercase())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Perfect => "perfect",
+            Difficulty::Adaptive => "adaptive",
+        }
+    }
+
+    /// Chance of the bot playing a random move instead of the best one. None for adaptive,
+    /// whose rate comes from the profile.
+    pub fn blunder_rate(&self) -> Option<f64> {
+        match self {
+            Difficulty::Easy => Some(0.6),
+            Difficulty::Medium => Some(0.3),
+            Difficulty::Hard => Some(0.1),
+            Difficulty::Perfect => Some(0.0),
+            Difficulty::Adaptive => None,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,124 @@
+//! What can go wrong, by cause. Each kind carries the message shown to the player, already translated.
+//! Functions that can only fail one way return that kind; the rest return `GameError`, which any of
+//! them converts into with `?`.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+/// Text that doesn't read as what it should be: a move, a position, a flag, a config or data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    /// The bytes of the input the error is about, if it is about one part of it.
+    span: Option<Range<usize>>,
+}
+
+/// A move or command the rules don't allow in the position, such as playing on a taken cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveError {
+    message: String,
+}
+
+/// Reading or writing the console or a file failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IoError {
+    message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    Parse(ParseError),
+    Move(MoveError),
+    Io(IoError),
+}
+
+impl ParseError {
+    pub fn new(message: &str) -> ParseError {
+        ParseError { message: message.to_string(), span: None }
+    }
+
+    /// The same error, pointing at the bytes of the input it is about.
+    pub fn at(self, span: Range<usize>) -> ParseError {
+        ParseError { span: Some(span), ..self }
+    }
+
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+}
+
+impl MoveError {
+    pub fn new(message: &str) -> MoveError {
+        MoveError { message: message.to_string() }
+    }
+}
+
+impl IoError {
+    pub fn new(message: &str) -> IoError {
+        IoError { message: message.to_string() }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Display for IoError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The message of the error inside.
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            GameError::Parse(e) => write!(f, "{}", e),
+            GameError::Move(e) => write!(f, "{}", e),
+            GameError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Error for MoveError {}
+
+impl Error for IoError {}
+
+impl Error for GameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GameError::Parse(e) => Some(e),
+            GameError::Move(e) => Some(e),
+            GameError::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for GameError {
+    fn from(e: ParseError) -> GameError {
+        GameError::Parse(e)
+    }
+}
+
+impl From<MoveError> for GameError {
+    fn from(e: MoveError) -> GameError {
+        GameError::Move(e)
+    }
+}
+
+impl From<IoError> for GameError {
+    fn from(e: IoError) -> GameError {
+        GameError::Io(e)
+    }
+}
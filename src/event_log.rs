@@ -0,0 +1,143 @@
+//! Every game, as what happened in it, appended to `logs/<start>.log` under the data directory as it happens,
+//! one event a line after the milliseconds since the Unix epoch:
+//!
+//! ```text
+//! 1760536800120 start ..././... O
+//! 1760536802511 move O 2-2
+//! 1760536803040 move X 1-1
+//! 1760536809733 undo 2
+//! 1760536811902 clock O 48.2
+//! 1760536815377 resume
+//! 1760536830004 over O line
+//! ```
+//!
+//! `<start>` is the second the game began, as `started` in the game archive, which is how `games log <id>`
+//! finds the log of a saved game. The final position follows from the log alone, so a game can be set up
+//! again and its saved result checked against what was played.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::archive::{self, parse_player, seconds};
+use crate::config::data_dir;
+use crate::error::{GameError, IoError, ParseError};
+use crate::events::{Event, Observer};
+use crate::game_result::Termination;
+use crate::generate::play;
+use crate::i18n::tr_args;
+use crate::{parse_coordinates, say, Board, Winner};
+
+/// Appends the events of the game to its log.
+pub struct EventLog {
+    path: PathBuf,
+}
+
+impl EventLog {
+    /// Starts the log of the game that began at `began` from the position on the board, or carries it on if
+    /// the game is being resumed.
+    pub fn start(board: &Board, began: SystemTime) -> Result<EventLog, IoError> {
+        let log = EventLog { path: path(seconds(began)) };
+        let line = if log.path.exists() { "resume".to_string() } else { format!("start {}", board.to_compact_string()) };
+        log.append(&line)?;
+        Ok(log)
+    }
+
+    fn append(&self, line: &str) -> Result<(), IoError> {
+        let millis = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_millis());
+        self.path
+            .parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| OpenOptions::new().create(true).append(true).open(&self.path))
+            .and_then(|mut file| writeln!(file, "{} {}", millis, line))
+            .map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &self.path.display())])))
+    }
+}
+
+impl Observer for EventLog {
+    fn notify(&mut self, event: &Event, board: &Board) {
+        let line = match event {
+            Event::MovePlayed { player, coord } => format!("move {} {}", player, coord),
+            Event::UndoPerformed { moves } => format!("undo {}", moves),
+            Event::ClockTick { player, remaining } => format!("clock {} {:.1}", player, remaining.as_secs_f64()),
+            Event::GameOver(winner) => {
+                let winner = match winner {
+                    Winner::Player(player) => player.to_string(),
+                    Winner::Draw => "draw".to_string(),
+                };
+                let termination = board.termination.unwrap_or(Termination::Line);
+                format!("over {} {}", winner, termination.name())
+            }
+        };
+        if let Err(e) = self.append(&line) {
+            say(tr_args("error.prefix", &[("message", &e)]));
+        }
+    }
+}
+
+/// The board at the end of the log, from the position it starts at and every event after it.
+pub fn final_board(text: &str) -> Result<Board, ParseError> {
+    let mut lines = text.lines().map(|line| line.split_whitespace().skip(1).collect::<Vec<&str>>());
+    let mut board = match lines.next().as_deref() {
+        Some(["start", cells, turn]) => Board::from_compact(&format!("{} {}", cells, turn))?,
+        _ => return Err(invalid(1)),
+    };
+    for (number, fields) in lines.enumerate() {
+        let error = || invalid(number + 2);
+        match fields[..] {
+            ["move", player, coord] => {
+                let coord = parse_coordinates(coord.to_string()).map_err(|_| error())?;
+                if parse_player(player) != Some(board.turn) || board.winner.is_some() || board.get_cell(coord).is_some() {
+                    return Err(error());
+                }
+                play(&mut board, coord);
+            }
+            ["undo", moves] => {
+                for _ in 0..moves.parse::<usize>().map_err(|_| error())? {
+                    board.undo().ok_or_else(error)?;
+                }
+            }
+            ["over", winner, termination] => {
+                let winner = match winner {
+                    "draw" => Winner::Draw,
+                    player => Winner::Player(parse_player(player).ok_or_else(error)?),
+                };
+                let termination = Termination::from_name(termination).ok_or_else(error)?;
+                if board.winner.is_none() {
+                    board.finish(winner, termination);
+                }
+            }
+            ["clock", _, _] | ["resume"] => {}
+            _ => return Err(error()),
+        }
+    }
+    Ok(board)
+}
+
+/// Shows the log of a saved game and whether the game was saved as it was played.
+pub fn show(id: &str) -> Result<(), GameError> {
+    let game = archive::find(id)?;
+    let path = path(game.started);
+    let text = fs::read_to_string(&path).map_err(|_| IoError::new(&tr_args("error.read_file", &[("path", &path.display())])))?;
+    let first = text.split_whitespace().next().and_then(|millis| millis.parse::<u64>().ok()).unwrap_or(0);
+    for line in text.lines() {
+        let (millis, event) = line.split_once(' ').unwrap_or((line, ""));
+        let elapsed = millis.parse::<u64>().unwrap_or(first).saturating_sub(first);
+        println!("{:>8.1}s  {}", elapsed as f64 / 1000.0, event);
+    }
+    let board = final_board(&text)?;
+    println!("{}", board);
+    let key = if board.cells == game.final_board()?.cells && board.winner == Some(game.winner) { "log.matches" } else { "log.differs" };
+    println!("{}", tr_args(key, &[("id", &game.id)]));
+    Ok(())
+}
+
+fn invalid(line: usize) -> ParseError {
+    ParseError::new(&tr_args("error.log_line", &[("line", &line)]))
+}
+
+fn path(started: u64) -> PathBuf {
+    data_dir().join("logs").join(format!("{}.log", started))
+}
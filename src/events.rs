@@ -0,0 +1,62 @@
+//! What happens in the game in progress, told to whoever subscribed, so that saving, sound cues and other
+//! frontends follow the game from one place instead of each comparing boards on its own. An observer is
+//! anything implementing `Observer`; a `Sender` is one, for a thread that wants the events on a channel.
+
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use crate::{Board, Coordinate, Player, Winner};
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Event {
+    MovePlayed { player: Player, coord: Coordinate },
+    /// Moves taken back, a move pair for each takeback.
+    UndoPerformed { moves: usize },
+    /// A turn on the clock is over, with the time the player has left.
+    ClockTick { player: Player, remaining: Duration },
+    GameOver(Winner),
+}
+
+pub trait Observer {
+    /// Called after the event, with the board as it is then.
+    fn notify(&mut self, event: &Event, board: &Board);
+}
+
+impl Observer for Sender<Event> {
+    fn notify(&mut self, event: &Event, _: &Board) {
+        // A receiver that has gone away just stops getting events
+        let _ = self.send(*event);
+    }
+}
+
+#[derive(Default)]
+pub struct Events {
+    observers: Vec<Box<dyn Observer>>,
+}
+
+impl Events {
+    pub fn subscribe(&mut self, observer: Box<dyn Observer>) {
+        self.observers.push(observer);
+    }
+
+    pub fn emit(&mut self, event: Event, board: &Board) {
+        for observer in &mut self.observers {
+            observer.notify(&event, board);
+        }
+    }
+
+    /// Emits what took the game from one board to the other: the moves taken back, then the moves played,
+    /// then the end of the game if it has just ended.
+    pub fn emit_changes(&mut self, before: &Board, after: &Board) {
+        let kept = (0..before.moves.min(after.moves)).take_while(|&i| before.history[i] == after.history[i]).count();
+        if before.moves > kept {
+            self.emit(Event::UndoPerformed { moves: before.moves - kept }, after);
+        }
+        for &(player, coord) in after.history[kept..after.moves].iter().flatten() {
+            self.emit(Event::MovePlayed { player, coord }, after);
+        }
+        if let (None, Some(winner)) = (before.winner, after.winner) {
+            self.emit(Event::GameOver(winner), after);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{Board, Coordinate, Player, Winner};
+
+/// Finds the move with the best expected score against an opponent who plays a random move
+/// with probability `epsilon` and their best move otherwise. An epsilon of 1 is a fully random opponent.
+/// The search plays on a copy of the board, leaving the caller's history alone.
+pub fn best_move(board: &Board, ai_player: Player, epsilon: f64) -> Option<(Coordinate, usize)> {
+    let mut position = *board;
+    let board = &mut position;
+    let mut counter: usize = 0;
+    let mut best: Option<(Coordinate, f64)> = None;
+
+    for coord in board.empty_cells() {
+        let score = score_after(board, coord, ai_player, epsilon, 0, &mut counter);
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((coord, score));
+        }
+    }
+
+    best.map(|(coord, _)| (coord, counter))
+}
+
+/// Plays the move, scores the resulting position, and takes the move back.
+fn score_after(board: &mut Board, coord: Coordinate, ai_player: Player, epsilon: f64, depth: i32, counter: &mut usize) -> f64 {
+    board.play_move(coord);
+    board.turn = board.turn.opponent();
+    board.check_if_game_over();
+
+    let score = expected_score(board, ai_player, epsilon, depth + 1, counter);
+
+    board.undo();
+    score
+}
+
+fn expected_score(board: &mut Board, ai_player: Player, epsilon: f64, depth: i32, counter: &mut usize) -> f64 {
+    *counter += 1;
+
+    // Same scale as min_max, so quicker wins are still preferred
+    if let Some(winner) = board.winner {
+        return match winner {
+            Winner::Player(p) if p == ai_player => f64::from(10 - depth),
+            Winner::Player(_) => f64::from(depth - 10),
+            Winner::Draw => 0.0,
+        };
+    }
+
+    let moves = board.empty_cells();
+    let scores: Vec<f64> = moves
+        .iter()
+        .map(|&coord| score_after(board, coord, ai_player, epsilon, depth, counter))
+        .collect();
+
+    if board.turn == ai_player {
+        scores.iter().copied().fold(f64::MIN, f64::max)
+    } else {
+        let average = scores.iter().sum::<f64>() / scores.len() as f64;
+        let best_reply = scores.iter().copied().fold(f64::MAX, f64::min);
+        epsilon * average + (1.0 - epsilon) * best_reply
+    }
+}
@@ -0,0 +1,124 @@
+//! Looking back over a finished game: rewind to any move, try something else against the engine, and
+//! come back to the game as it was played. Every line tried is kept as a variation, apart from the game.
+//!
+//! ```text
+//! goto <n>   the position after move n of the game, 0 for the start
+//! <row-col>  play a move there, the engine answers
+//! main       back to the end of the game as played
+//! list       the variations tried so far
+//! done       stop exploring
+//! ```
+
+use crate::i18n::{tr, tr_args};
+use crate::{ai_best_move, get_input_from_console, parse_coordinates, say, Board, Coordinate};
+
+/// A line tried instead of the game's own moves.
+#[derive(Clone)]
+pub struct Variation {
+    /// How many moves of the game were played before the variation branched off.
+    pub from: usize,
+    pub moves: Vec<Coordinate>,
+}
+
+impl Variation {
+    /// The moves, e.g. `2-2 1-3 3-1`.
+    pub fn moves_text(&self) -> String {
+        self.moves.iter().map(Coordinate::to_string).collect::<Vec<String>>().join(" ")
+    }
+}
+
+/// The position after the first `count` moves of the game.
+fn position_after(start: &Board, moves: &[Coordinate], count: usize) -> Board {
+    let mut board = *start;
+    for &coord in &moves[..count.min(moves.len())] {
+        board.play_move(coord);
+        board.turn = board.turn.opponent();
+        board.check_if_game_over();
+    }
+    board
+}
+
+/// Runs the exploration until the player is done, returning the variations they tried.
+pub fn explore(start: &Board, game: &[Coordinate]) -> Vec<Variation> {
+    let mut variations: Vec<Variation> = Vec::new();
+    // The position being looked at: how far into the game, and the moves tried from there
+    let mut current = Variation { from: game.len(), moves: Vec::new() };
+    let mut board = position_after(start, game, game.len());
+
+    say(tr("explore.help"));
+    say(board);
+    while let Ok(input) = get_input_from_console() {
+        let input = input.trim().to_lowercase();
+        let (command, argument) = input.split_once(char::is_whitespace).unwrap_or((&input, ""));
+        match command {
+            "done" | "quit" => break,
+            "list" => {
+                if variations.is_empty() {
+                    say(tr("explore.no_variations"));
+                }
+                for (number, variation) in variations.iter().enumerate() {
+                    say(tr_args("explore.variation", &[("number", &(number + 1)), ("move", &variation.from), ("moves", &variation.moves_text())]));
+                }
+                continue;
+            }
+            "main" | "goto" => {
+                let count = if command == "main" { Some(game.len()) } else { argument.trim().parse().ok().filter(|&count| count <= game.len()) };
+                let Some(count) = count else {
+                    say(tr_args("error.prefix", &[("message", &tr_args("explore.bad_move_number", &[("moves", &game.len())]))]));
+                    continue;
+                };
+                keep(&mut variations, &mut current);
+                current.from = count;
+                board = position_after(start, game, count);
+            }
+            _ => {
+                let coord = match parse_coordinates(input.clone()) {
+                    Ok(coord) if board.winner.is_none() && board.get_cell(coord).is_none() => coord,
+                    Ok(_) => {
+                        say(tr_args("error.prefix", &[("message", &tr("error.cell_taken"))]));
+                        continue;
+                    }
+                    Err(_) => {
+                        say(tr_args("error.prefix", &[("message", &tr("explore.unknown_command"))]));
+                        continue;
+                    }
+                };
+                // Following the game's own move is not a variation
+                let played = current.from + current.moves.len();
+                if current.moves.is_empty() && game.get(played) == Some(&coord) {
+                    current.from += 1;
+                    board = position_after(start, game, current.from);
+                } else {
+                    play(&mut board, &mut current, coord);
+                    if board.winner.is_none() {
+                        let engine = board.turn;
+                        let (reply, _) = ai_best_move(&board, engine);
+                        say(tr_args("explore.engine", &[("move", &reply)]));
+                        play(&mut board, &mut current, reply);
+                    }
+                }
+            }
+        }
+        say(board);
+        if let Some(winner) = board.winner {
+            say(winner);
+        }
+    }
+    keep(&mut variations, &mut current);
+    variations
+}
+
+fn play(board: &mut Board, variation: &mut Variation, coord: Coordinate) {
+    board.play_move(coord);
+    board.turn = board.turn.opponent();
+    board.check_if_game_over();
+    variation.moves.push(coord);
+}
+
+/// Adds the variation being played to the list, if any moves were tried in it.
+fn keep(variations: &mut Vec<Variation>, current: &mut Variation) {
+    if !current.moves.is_empty() {
+        variations.push(current.clone());
+        current.moves.clear();
+    }
+}
@@ -0,0 +1,103 @@
+//! Results as tables for spreadsheets and data tools, written by `--export <file>` of `swiss` and `gauntlet`:
+//! a row for every game played and a summary row for every bot.
+//!
+//! A file ending in `.json` gets both tables in one object, each an array of rows keyed by column:
+//!
+//! ```text
+//! {"games": [{"game": 1, "o": "hard", "x": "easy", "winner": "O", ...}], "summary": [...]}
+//! ```
+//!
+//! Any other file gets the games as CSV with a header line, and the summary goes to a second CSV file next
+//! to it, named after it with `-summary`: `results.csv` and `results-summary.csv`.
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::IoError;
+use crate::i18n::tr_args;
+
+pub enum Value {
+    Text(String),
+    Number(f64),
+    /// Empty in CSV, `null` in JSON.
+    Missing,
+}
+
+pub struct Table {
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Writes the games and the summary to the file, and to the summary file for CSV. Returns the files written.
+pub fn write(path: &str, games: &Table, summary: &Table) -> Result<Vec<String>, IoError> {
+    let write = |path: &str, text: String| fs::write(path, text).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &path)])));
+    let file = Path::new(path);
+    if file.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json")) {
+        write(path, format!("{{\"games\": {}, \"summary\": {}}}\n", to_json(games), to_json(summary)))?;
+        return Ok(vec![path.to_string()]);
+    }
+
+    let stem = file.file_stem().map_or(String::new(), |stem| stem.to_string_lossy().to_string());
+    let extension = file.extension().map_or(String::new(), |extension| format!(".{}", extension.to_string_lossy()));
+    let summary_path = file.with_file_name(format!("{}-summary{}", stem, extension)).to_string_lossy().to_string();
+    write(path, to_csv(games))?;
+    write(&summary_path, to_csv(summary))?;
+    Ok(vec![path.to_string(), summary_path])
+}
+
+fn to_csv(table: &Table) -> String {
+    let mut text = format!("{}\n", table.columns.join(","));
+    for row in &table.rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|value| match value {
+                // Quoted only when it has to be, doubling the quotes inside
+                Value::Text(text) if text.contains([',', '"', '\n']) => format!("\"{}\"", text.replace('"', "\"\"")),
+                Value::Text(text) => text.clone(),
+                Value::Number(number) => number.to_string(),
+                Value::Missing => String::new(),
+            })
+            .collect();
+        text.push_str(&cells.join(","));
+        text.push('\n');
+    }
+    text
+}
+
+fn to_json(table: &Table) -> String {
+    let rows: Vec<String> = table
+        .rows
+        .iter()
+        .map(|row| {
+            let fields: Vec<String> = table
+                .columns
+                .iter()
+                .zip(row)
+                .map(|(column, value)| {
+                    let value = match value {
+                        Value::Text(text) => json_string(text),
+                        Value::Number(number) => number.to_string(),
+                        Value::Missing => "null".to_string(),
+                    };
+                    format!("{}: {}", json_string(column), value)
+                })
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        })
+        .collect();
+    format!("[{}]", rows.join(", "))
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
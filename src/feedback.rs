@@ -0,0 +1,67 @@
+//! What a player sees when what they typed on their turn can't be used: their input again with the
+//! problem underlined, the reason, and the empty cells closest to the one they seem to have meant.
+//!
+//! ```text
+//!   4-2
+//!   ^
+//! Error: Row index out of range
+//! Empty cells nearby: 3-2, 2-2, 3-1
+//! ```
+
+use std::ops::Range;
+
+use crate::coordinates::{digit_layout, row_and_column, tokenize};
+use crate::error::GameError;
+use crate::i18n::tr_args;
+use crate::{say, Board, Coordinate};
+
+/// Empty cells suggested at most.
+const SUGGESTIONS: usize = 3;
+
+pub fn show(board: &Board, input: &str, error: &GameError) {
+    let input = input.trim();
+    if !input.is_empty() {
+        let span = match error {
+            GameError::Parse(e) => e.span(),
+            _ => None,
+        };
+        say(format!("  {}", input));
+        say(format!("  {}", underline(input, span.unwrap_or(0..input.len()))));
+    }
+    say(tr_args("error.prefix", &[("message", error)]));
+
+    let nearby = nearby_empty_cells(board, input);
+    if !nearby.is_empty() {
+        let cells: Vec<String> = nearby.iter().map(Coordinate::to_string).collect();
+        say(tr_args("input.nearby", &[("cells", &cells.join(", "))]));
+    }
+}
+
+/// Carets under the characters of the span, spaces before them.
+fn underline(input: &str, span: Range<usize>) -> String {
+    let width = |text: &str| text.chars().count();
+    let start = input.get(..span.start).map_or(0, width);
+    let length = input.get(span).map_or(0, width).max(1);
+    format!("{}{}", " ".repeat(start), "^".repeat(length))
+}
+
+/// The empty cells closest to the cell the input names, in any of the ways a move can be written, with its
+/// row and column each pulled into the board if they are off it. Nothing if the input doesn't name a cell, or
+/// names an empty cell on the board.
+fn nearby_empty_cells(board: &Board, input: &str) -> Vec<Coordinate> {
+    let named = tokenize(input).and_then(|tokens| row_and_column(&tokens, digit_layout()));
+    let Ok(((row, _), (col, _))) = named else {
+        return Vec::new();
+    };
+    if Coordinate::try_from((row.wrapping_sub(1), col.wrapping_sub(1))).is_ok_and(|coord| board.get_cell(coord).is_none()) {
+        return Vec::new();
+    }
+    let meant = |number: usize| number.clamp(1, 3) - 1;
+    let (row, col) = (meant(row), meant(col));
+
+    let mut cells = board.empty_cells();
+    let distance = |coord: &Coordinate| (coord.row as usize).abs_diff(row) + (coord.col as usize).abs_diff(col);
+    cells.sort_by_key(|coord| (distance(coord), coord.index()));
+    cells.truncate(SUGGESTIONS);
+    cells
+}
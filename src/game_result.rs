@@ -0,0 +1,106 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+use crate::analysis::format_cells;
+use crate::i18n::tr_args;
+use crate::profile::Outcome;
+use crate::{Board, Line, Player, Winner};
+
+/// How a game came to an end.
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+pub enum Termination {
+    /// Three in a row.
+    Line,
+    /// The board filled up without a line.
+    Draw,
+    /// Both players agreed to a draw.
+    Agreement,
+    Resignation,
+    /// A player ran out of time or idled past the timeout.
+    Timeout,
+}
+
+impl Termination {
+    pub const ALL: [Termination; 5] =
+        [Termination::Line, Termination::Draw, Termination::Agreement, Termination::Resignation, Termination::Timeout];
+
+    pub fn from_name(name: &str) -> Option<Termination> {
+        Termination::ALL.iter().copied().find(|termination| termination.name() == name.trim())
+    }
+
+    /// The name saved in the game archive.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Termination::Line => "line",
+            Termination::Draw => "draw",
+            Termination::Agreement => "agreement",
+            Termination::Resignation => "resignation",
+            Termination::Timeout => "timeout",
+        }
+    }
+}
+
+/// Everything worth knowing about a finished game.
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    pub winner: Winner,
+    pub winning_line: Option<Line>,
+    pub moves: usize,
+    pub termination: Termination,
+    /// Thinking time per player, indexed by player.
+    pub time: [Duration; 2],
+    /// Positions searched per player, indexed by player. Always zero for humans.
+    pub nodes: [usize; 2],
+}
+
+impl GameResult {
+    /// Reads the result off a finished board. None if the game isn't over.
+    pub fn new(board: &Board, time: [Duration; 2], nodes: [usize; 2]) -> Option<GameResult> {
+        let winner = board.winner?;
+        let winning_line = board.winning_line();
+        let termination = board.termination.unwrap_or(if winning_line.is_some() { Termination::Line } else { Termination::Draw });
+        // Counts the marks rather than the history, so games started from a position count its moves too
+        let moves = board.cells.iter().filter(|cell| cell.is_some()).count();
+        Some(GameResult { winner, winning_line, moves, termination, time, nodes })
+    }
+
+    /// How the game ended, in one line.
+    pub fn headline(&self) -> String {
+        let winner = self.winner.to_string();
+        match self.termination {
+            Termination::Line => tr_args(
+                "result.line",
+                &[("winner", &winner), ("moves", &self.moves), ("cells", &format_cells(&self.winning_line.unwrap_or_default()))],
+            ),
+            Termination::Draw => tr_args("result.full_board", &[("winner", &winner), ("moves", &self.moves)]),
+            Termination::Agreement => tr_args("result.agreement", &[("winner", &winner)]),
+            Termination::Resignation => tr_args("result.resignation", &[("winner", &winner)]),
+            Termination::Timeout => tr_args("result.timeout", &[("winner", &winner)]),
+        }
+    }
+
+    /// The result as seen by one of the players.
+    pub fn outcome_for(&self, player: Player) -> Outcome {
+        match self.winner {
+            Winner::Player(winner) if winner == player => Outcome::Win,
+            Winner::Player(_) => Outcome::Loss,
+            Winner::Draw => Outcome::Draw,
+        }
+    }
+}
+
+/// How the game ended, then the time and, if any bot searched, the positions each side used.
+impl Display for GameResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.headline())?;
+
+        let seconds = |player: Player| format!("{:.1}s", self.time[player as usize].as_secs_f64());
+        let (x, o) = (Player::X.name(), Player::O.name());
+        write!(f, "{}", tr_args("result.time", &[("x", &x), ("x_time", &seconds(Player::X)), ("o", &o), ("o_time", &seconds(Player::O))]))?;
+        if self.nodes.iter().any(|&count| count > 0) {
+            let nodes = |player: Player| self.nodes[player as usize];
+            write!(f, "\n{}", tr_args("result.nodes", &[("x", &x), ("x_nodes", &nodes(Player::X)), ("o", &o), ("o_nodes", &nodes(Player::O))]))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! Random legal games and positions, for checking the rules, the solver and the symmetry code over many
+//! boards at once, as the tests in `properties` do, or for proptest and quickcheck strategies built on
+//! them. Given the same seeded generator they come out the same, so a board that breaks something can be
+//! found again.
+
+use rand::Rng;
+
+use crate::{random_move, Board, Coordinate};
+
+/// Moves played at random from the position until the game is over.
+pub fn random_game(start: &Board, rng: &mut impl Rng) -> Vec<Coordinate> {
+    let mut board = *start;
+    let mut moves = Vec::new();
+    while board.winner.is_none() {
+        let Some(coord) = random_move(&board, rng) else {
+            break;
+        };
+        play(&mut board, coord);
+        moves.push(coord);
+    }
+    moves
+}
+
+/// A position reached by random moves from the empty board, anywhere from the first move to the end
+/// of the game. The moves are in its history, so they can be taken back.
+pub fn random_position(rng: &mut impl Rng) -> Board {
+    let mut board = Board::new();
+    let game = random_game(&board, rng);
+    for &coord in &game[..rng.gen_range(0..=game.len())] {
+        play(&mut board, coord);
+    }
+    board
+}
+
+/// Plays the move and passes the turn, as in a game.
+pub fn play(board: &mut Board, coord: Coordinate) {
+    board.play_move(coord);
+    board.turn = board.turn.opponent();
+    board.check_if_game_over();
+}
@@ -0,0 +1,57 @@
+//! Rules that hold a bot back on its first move while it plays perfectly afterwards, so beginners get a
+//! chance against the strongest bots. Set per agent with `handicap = no-center, random-first` or for the
+//! default bot with `--handicap`.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::analysis::move_values;
+use crate::{Board, Coordinate, Player};
+
+const CENTER: usize = 4;
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+pub enum Handicap {
+    /// The bot may not take the center with its first move.
+    NoCenter,
+    /// The bot's first move is random.
+    RandomFirst,
+}
+
+impl Handicap {
+    pub const ALL: [Handicap; 2] = [Handicap::NoCenter, Handicap::RandomFirst];
+
+    pub fn from_name(name: &str) -> Option<Handicap> {
+        Handicap::ALL.iter().copied().find(|handicap| handicap.name() == name.trim().to_lowercase())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Handicap::NoCenter => "no-center",
+            Handicap::RandomFirst => "random-first",
+        }
+    }
+}
+
+/// The bot's first move under its handicaps. None once the bot has moved, or if the handicaps
+/// don't change what it would play.
+pub fn handicap_move(board: &Board, player: Player, handicaps: &[Handicap], rng: &mut impl Rng) -> Option<Coordinate> {
+    if handicaps.is_empty() || board.cells.contains(&Some(player)) {
+        return None;
+    }
+    let no_center = handicaps.contains(&Handicap::NoCenter);
+    let allowed: Vec<Coordinate> =
+        board.empty_cells().into_iter().filter(|coord| !(no_center && coord.index() == CENTER)).collect();
+
+    if handicaps.contains(&Handicap::RandomFirst) {
+        return allowed.choose(rng).copied();
+    }
+    if board.cells[CENTER].is_some() {
+        return None;
+    }
+    // The best move off the center, picked at random among equals
+    let values = move_values(board);
+    let best = allowed.iter().filter_map(|coord| values[coord.index()]).max()?;
+    let best_moves: Vec<Coordinate> = allowed.into_iter().filter(|coord| values[coord.index()] == Some(best)).collect();
+    best_moves.choose(rng).copied()
+}
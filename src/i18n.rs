@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// Accepts both plain codes ("es") and locale strings as found in LANG ("es_ES.UTF-8").
+    pub fn from_code(code: &str) -> Option<Language> {
+        let code = code.trim().to_lowercase();
+        let prefix = code.split(['_', '-', '.']).next().unwrap_or("");
+        match prefix {
+            "en" => Some(Language::English),
+            "es" => Some(Language::Spanish),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../lang/en.txt"),
+            Language::Spanish => include_str!("../lang/es.txt"),
+        }
+    }
+
+    fn index(&self) -> usize {
+        Language::ALL.iter().position(|language| language == self).unwrap_or(0)
+    }
+}
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static CATALOGS: OnceLock<Vec<HashMap<&'static str, String>>> = OnceLock::new();
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language.index(), Ordering::Relaxed);
+}
+
+/// Picks the language from the LANG environment variable, falling back to English.
+pub fn language_from_env() -> Language {
+    env::var("LANG").ok().and_then(|lang| Language::from_code(&lang)).unwrap_or(Language::English)
+}
+
+fn catalogs() -> &'static Vec<HashMap<&'static str, String>> {
+    CATALOGS.get_or_init(|| Language::ALL.iter().map(|language| parse_catalog(language.source())).collect())
+}
+
+fn parse_catalog(source: &'static str) -> HashMap<&'static str, String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim().replace("\\n", "\n")))
+        .collect()
+}
+
+/// Looks up a message in the current language, falling back to English and then to the key itself.
+pub fn tr(key: &'static str) -> &'static str {
+    let catalogs = catalogs();
+    catalogs[CURRENT.load(Ordering::Relaxed)]
+        .get(key)
+        .or_else(|| catalogs[Language::English.index()].get(key))
+        .map(|message| message.as_str())
+        .unwrap_or(key)
+}
+
+/// Like `tr`, replacing each `{name}` placeholder with its value.
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut message = tr(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
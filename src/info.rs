@@ -0,0 +1,83 @@
+//! What this build of the game can do, for frontends and tournament harnesses to find out without
+//! parsing the help:
+//!
+//! ```text
+//! --version [--features]
+//! ```
+//!
+//! `--version` prints the name and version. With `--features` it also lists what every option accepts,
+//! in the config file format, one `key = a, b, c` line each, so that `Config::parse` reads it back.
+
+use crate::commands::COMMANDS;
+use crate::coordinates::DigitLayout;
+use crate::difficulty::Difficulty;
+use crate::error::{GameError, ParseError};
+use crate::handicap::Handicap;
+use crate::i18n::{tr_args, Language};
+use crate::render::{BoardStyle, CellNumbers, Flip, MAX_SCALE};
+use crate::strategy::Strategy;
+use crate::theme::Theme;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The commands given as the first argument, besides playing a game.
+const SUBCOMMANDS: &[&str] = &[
+    "quick",
+    "selfplay",
+    "swiss",
+    "gauntlet",
+    "replay",
+    "games",
+    "stats",
+    "leaderboard",
+    "correspond",
+    "practice",
+    "puzzles",
+    "count-positions",
+    "strategy-table",
+    "export-svg",
+    "share",
+    "irc",
+];
+
+/// The capabilities of the build, each a name with the values it comes in.
+pub fn features() -> Vec<(&'static str, Vec<String>)> {
+    vec![
+        ("version", vec![VERSION.to_string()]),
+        ("strategies", names(&Strategy::ALL, Strategy::name)),
+        ("difficulties", names(&Difficulty::ALL, Difficulty::name)),
+        ("handicaps", names(&Handicap::ALL, Handicap::name)),
+        ("boards", names(&BoardStyle::ALL, BoardStyle::name)),
+        ("themes", names(&Theme::ALL, Theme::name)),
+        ("scales", (1..=MAX_SCALE).map(|scale| scale.to_string()).collect()),
+        ("numbers", names(&CellNumbers::ALL, CellNumbers::name)),
+        ("flips", names(&Flip::ALL, Flip::name)),
+        ("digits", names(&DigitLayout::ALL, DigitLayout::name)),
+        ("languages", names(&Language::ALL, Language::code)),
+        ("commands", SUBCOMMANDS.iter().map(|name| name.to_string()).collect()),
+        ("game_commands", COMMANDS.iter().map(|command| command.name.to_string()).collect()),
+    ]
+}
+
+fn names<T>(all: &[T], name: fn(&T) -> &'static str) -> Vec<String> {
+    all.iter().map(|value| name(value).to_string()).collect()
+}
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut show_features = false;
+    for arg in args {
+        match arg.as_str() {
+            "--features" => show_features = true,
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    if !show_features {
+        println!("tic-tac-toe {}", VERSION);
+        return Ok(());
+    }
+    for (name, values) in features() {
+        println!("{} = {}", name, values.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Tic-tac-toe in an IRC channel, played the same way as in the terminal: the board is drawn by the ASCII
+//! renderer as monospaced lines, moves are read from chat messages with the same parser, and the opponent is
+//! any agent.
+//!
+//! ```text
+//! irc <host:port> <#channel> [--nick <nick>] [--agent <name>]
+//! ```
+//!
+//! In the channel, `!new` starts a game against the bot, the player who typed it moving first as O.
+//! `!move <cell>` plays a move written in any of the forms of `coordinates`, `!board` shows the board again
+//! and `!resign` gives the game up. One game runs at a time, and only its player moves in it.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use crate::agent::{parse_seat, Agent, Seat};
+use crate::config::Config;
+use crate::error::{GameError, IoError, MoveError, ParseError};
+use crate::game_result::{GameResult, Termination};
+use crate::generate::play;
+use crate::i18n::{tr, tr_args};
+use crate::options::{flag_value, Options};
+use crate::render::BoardStyle;
+use crate::theme::Theme;
+use crate::{choose_bot_move, parse_coordinates, Board, Bot, Player, Winner};
+
+const DEFAULT_NICK: &str = "tictactoe";
+
+/// The game running in the channel.
+struct Game {
+    /// Nick of the human, who plays O.
+    player: String,
+    board: Board,
+    bot: Bot,
+}
+
+/// The connection to the server, and the channel the game is played in.
+struct Chat {
+    stream: TcpStream,
+    channel: String,
+}
+
+impl Chat {
+    fn send(&mut self, line: &str) -> Result<(), IoError> {
+        write!(self.stream, "{}\r\n", line).map_err(|_| IoError::new(tr("error.irc_connection")))
+    }
+
+    /// Says the text in the channel, a message per line as IRC messages can't hold line breaks.
+    fn say(&mut self, text: &str) -> Result<(), IoError> {
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            self.send(&format!("PRIVMSG {} :{}", self.channel, line))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut address: Option<String> = None;
+    let mut channel: Option<String> = None;
+    let mut nick = DEFAULT_NICK.to_string();
+    let mut agent_name: Option<String> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--nick" => nick = flag_value(arg, args.next())?.trim().to_string(),
+            "--agent" => agent_name = Some(flag_value(arg, args.next())?.to_string()),
+            name if address.is_none() && !name.starts_with("--") => address = Some(name.to_string()),
+            name if channel.is_none() && name.starts_with('#') => channel = Some(name.to_string()),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+    let (Some(address), Some(channel)) = (address, channel) else {
+        return Err(ParseError::new(tr("error.irc_usage")).into());
+    };
+
+    let config = Config::load()?;
+    let options = Options::new(&[], &config)?;
+    let agent = match parse_seat(agent_name.as_deref().unwrap_or(options.difficulty.name()), &config, &options)? {
+        Seat::Bot(agent) => agent,
+        Seat::Human => return Err(ParseError::new(tr("error.irc_human")).into()),
+    };
+    // Build a bot before connecting, so a bad weights file fails right away
+    Bot::new(Player::X, &agent)?;
+
+    println!("{}", tr_args("irc.connecting", &[("address", &address), ("nick", &nick)]));
+    let stream = TcpStream::connect(&address).map_err(|_| IoError::new(&tr_args("error.irc_connect", &[("address", &address)])))?;
+    let reader = BufReader::new(stream.try_clone().map_err(|_| IoError::new(tr("error.irc_connection")))?);
+    let mut chat = Chat { stream, channel };
+    chat.send(&format!("NICK {}", nick))?;
+    chat.send(&format!("USER {} 0 * :tic-tac-toe", nick))?;
+
+    let mut game: Option<Game> = None;
+    for line in reader.lines() {
+        let line = line.map_err(|_| IoError::new(tr("error.irc_connection")))?;
+        let (sender, command, params) = parse_line(&line);
+        match (command, &params[..]) {
+            ("PING", [token, ..]) => chat.send(&format!("PONG :{}", token))?,
+            // Welcomed, so the nick was taken and the channel can be joined
+            ("001", _) => {
+                let join = format!("JOIN {}", chat.channel);
+                chat.send(&join)?;
+            }
+            ("PRIVMSG", [target, text]) if *target == chat.channel => {
+                if let Some(sender) = sender {
+                    handle(&mut chat, &mut game, &agent, sender, text)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The nick of the sender, the command and its parameters of a line from the server. The last parameter
+/// is everything after a ` :`, spaces included.
+fn parse_line(line: &str) -> (Option<&str>, &str, Vec<&str>) {
+    let (prefix, rest) = match line.strip_prefix(':') {
+        Some(rest) => rest.split_once(' ').map_or((Some(rest), ""), |(prefix, rest)| (Some(prefix), rest)),
+        None => (None, line),
+    };
+    let sender = prefix.map(|prefix| prefix.split('!').next().unwrap_or(prefix));
+    let (middle, trailing) = match rest.split_once(" :") {
+        Some((middle, trailing)) => (middle, Some(trailing)),
+        None => (rest.strip_suffix(':').unwrap_or(rest), None),
+    };
+    let mut words = middle.split_whitespace();
+    let command = words.next().unwrap_or_default();
+    let mut params: Vec<&str> = words.collect();
+    params.extend(trailing);
+    (sender, command, params)
+}
+
+/// Answers a message in the channel, if it is one of the game's commands.
+fn handle(chat: &mut Chat, game: &mut Option<Game>, agent: &Agent, sender: &str, text: &str) -> Result<(), IoError> {
+    let (command, argument) = text.trim().split_once(' ').unwrap_or((text.trim(), ""));
+    let running = game.as_ref().filter(|game| game.board.winner.is_none());
+    match (command, running) {
+        ("!new", Some(running)) if running.player != sender => chat.say(&tr_args("irc.busy", &[("player", &running.player)])),
+        ("!new", _) => {
+            let bot = match Bot::new(Player::X, agent) {
+                Ok(bot) => bot,
+                Err(e) => return chat.say(&tr_args("error.prefix", &[("message", &e)])),
+            };
+            let board = Board { theme: Theme::Monochrome, style: BoardStyle::Ascii, ..Board::new() };
+            chat.say(&tr_args("irc.started", &[("player", &sender), ("bot", &bot.name)]))?;
+            chat.say(&BoardStyle::Ascii.renderer().render(&board))?;
+            *game = Some(Game { player: sender.to_string(), board, bot });
+            Ok(())
+        }
+        ("!move" | "!board" | "!resign", None) => chat.say(tr("irc.no_game")),
+        ("!move" | "!resign", Some(running)) if running.player != sender => chat.say(&tr_args("irc.busy", &[("player", &running.player)])),
+        ("!board", Some(running)) => chat.say(&BoardStyle::Ascii.renderer().render(&running.board)),
+        ("!resign", Some(_)) => {
+            let Some(game) = game.as_mut() else { return Ok(()) };
+            game.board.finish(Winner::Player(Player::X), Termination::Resignation);
+            announce_result(chat, &game.board)
+        }
+        ("!move", Some(_)) => {
+            let Some(game) = game.as_mut() else { return Ok(()) };
+            if let Err(e) = play_move(game, argument) {
+                return chat.say(&tr_args("error.prefix", &[("message", &e)]));
+            }
+            // The bot answers straight away, unless the move ended the game
+            if game.board.winner.is_none() {
+                let (coord, _) = choose_bot_move(&mut game.board, &mut game.bot);
+                play(&mut game.board, coord);
+                chat.say(&tr_args("irc.bot_move", &[("bot", &game.bot.name), ("move", &coord)]))?;
+            }
+            chat.say(&BoardStyle::Ascii.renderer().render(&game.board))?;
+            announce_result(chat, &game.board)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Plays the player's move, written in any form `coordinates` reads.
+fn play_move(game: &mut Game, input: &str) -> Result<(), GameError> {
+    let coord = parse_coordinates(input.to_string())?;
+    if game.board.get_cell(coord).is_some() {
+        return Err(MoveError::new(tr("error.cell_taken")).into());
+    }
+    play(&mut game.board, coord);
+    Ok(())
+}
+
+/// Says how the game ended, if it has.
+fn announce_result(chat: &mut Chat, board: &Board) -> Result<(), IoError> {
+    match GameResult::new(board, Default::default(), Default::default()) {
+        Some(result) if board.winner.is_some() => chat.say(&result.headline()),
+        _ => Ok(()),
+    }
+}
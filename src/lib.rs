@@ -0,0 +1,1557 @@
+//! The game, and the parsers for what players type and the files they edit, for fuzzing them:
+//!
+//! - `parse_move` reads a move as typed, see `coordinates`.
+//! - `Board::from_compact` reads a position, as given with `--position`.
+//! - `Config::parse` reads the config file, and every other file saved in its format.
+//! - `Command::parse` reads a line typed on the player's turn.
+//! - `parse_games` reads a self-play file.
+//!
+//! The targets under `fuzz/` run them with `cargo fuzz`. `generate` makes random legal games and positions,
+//! for property tests.
+
+use std::io::{self, Write};
+use std::fmt::{self, Formatter, Display};
+use std::result::Result;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+mod agent;
+mod analysis;
+mod archive;
+mod autosave;
+mod bell;
+mod book;
+mod clock;
+mod commands;
+mod config;
+pub mod coordinates;
+mod correspond;
+mod difficulty;
+mod error;
+mod event_log;
+mod events;
+mod expectimax;
+mod export;
+mod explore;
+mod feedback;
+mod game_result;
+pub mod generate;
+mod handicap;
+mod i18n;
+mod info;
+mod irc;
+mod neural;
+mod opponent_model;
+mod options;
+mod ponder;
+mod positions;
+mod practice;
+mod profile;
+#[cfg(test)]
+mod properties;
+mod puzzles;
+mod qr;
+mod render;
+mod report;
+mod replay;
+mod rollout;
+mod save_format;
+mod selfplay;
+mod share;
+mod stats;
+mod strategy;
+mod svg;
+mod theme;
+mod transcript;
+mod tournament;
+mod tutorial;
+
+use agent::{Agent, Seat};
+use archive::SavedGame;
+use autosave::Session;
+use clock::Clock;
+use commands::Outcome;
+use error::{GameError, IoError};
+use event_log::EventLog;
+use events::{Event, Events};
+use game_result::{GameResult, Termination};
+use handicap::{handicap_move, Handicap};
+use i18n::{tr, tr_args};
+use neural::Network;
+use opponent_model::OpponentModel;
+use ponder::Ponderer;
+use options::Options;
+use profile::Profile;
+use render::{BoardStyle, CellNumbers, Flip};
+use strategy::Strategy;
+use theme::Theme;
+
+pub use commands::Command;
+pub use config::Config;
+pub use coordinates::parse as parse_move;
+pub use error::ParseError;
+pub use replay::{parse_games, Game};
+
+#[derive(PartialEq, Debug, Clone, Copy, Hash, Eq)]
+pub enum Winner {
+    Player(Player),
+    Draw
+}
+
+/// "X wins" or "Draw", in the current language.
+impl Display for Winner {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Winner::Player(player) => write!(f, "{}", tr_args("winner.player", &[("player", &player.name())])),
+            Winner::Draw => write!(f, "{}", tr("winner.draw")),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Eq)]
+pub struct Board {
+    cells: [Option<Player>; 9],
+    turn: Player,
+    winner: Option<Winner>,
+    highlight: Option<Coordinate>,
+    theme: Theme,
+    style: BoardStyle,
+    /// How big the grids draw the cells, 1 to `render::MAX_SCALE`.
+    scale: usize,
+    numbers: CellNumbers,
+    flip: Flip,
+    /// Moves in the order they were played. Entries past `moves` were undone and can be redone.
+    history: [Option<(Player, Coordinate)>; 9],
+    /// How many moves of the history are on the board.
+    moves: usize,
+    /// Set when the game was decided off the board, by resignation, agreement or the clock.
+    termination: Option<Termination>,
+    /// Move pairs taken back with `undo` during the game, for the game archive.
+    takebacks: usize,
+}
+
+/// A cell that holds something else on another board, for redrawing only what changed.
+#[derive(PartialEq, Clone, Copy, Eq)]
+struct CellChange {
+    coord: Coordinate,
+    before: Option<Player>,
+    after: Option<Player>,
+}
+
+impl Display for Player {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let _ = match self {
+            Player::X => write!(f, "X"),
+            Player::O => write!(f, "O"),
+        };
+        Ok(())
+    }
+}
+
+/// Draws the board with the renderer picked for it, the Unicode grid unless `--board` says otherwise.
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.style.renderer().render(self))
+    }
+}
+
+/// The empty board, O to move.
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Hash, Eq)]
+pub enum Player {
+    X,
+    O
+}
+
+static PLAYER_NAMES: OnceLock<[Option<String>; 2]> = OnceLock::new();
+
+/// Sets the names shown for X and O, in that order. Can only be set once.
+fn set_player_names(names: [Option<String>; 2]) {
+    let _ = PLAYER_NAMES.set(names);
+}
+
+impl Player {
+    fn opponent(&self) -> Player {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+
+    /// The name chosen for this player, or its mark if it has none.
+    fn name(&self) -> String {
+        match PLAYER_NAMES.get().and_then(|names| names[*self as usize].as_ref()) {
+            Some(name) => name.clone(),
+            None => self.to_string(),
+        }
+    }
+
+    /// The name together with the mark, e.g. "Alice (O)", for prompts where the player needs to know their mark.
+    fn label(&self) -> String {
+        let name = self.name();
+        if name == self.to_string() { name } else { format!("{} ({})", name, self) }
+    }
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self {
+            cells: [None; 9],
+            turn: Player::O,
+            winner: None,
+            highlight: None,
+            theme: Theme::Default,
+            style: BoardStyle::Pretty,
+            scale: 1,
+            numbers: CellNumbers::Off,
+            flip: Flip::None,
+            history: [None; 9],
+            moves: 0,
+            termination: None,
+            takebacks: 0,
+        }
+    }
+
+    fn highlight(&mut self, cell: Coordinate) {
+        self.highlight = Some(cell);
+    }
+
+    /// Puts the mark of the player to move on the cell and records it. Passing the turn is left to the caller.
+    /// A new move discards the moves that could have been redone.
+    fn play_move(&mut self, pl_move: Coordinate) {
+        self.cells[pl_move.index()] = Some(self.turn);
+        self.history[self.moves] = Some((self.turn, pl_move));
+        self.moves += 1;
+        for entry in &mut self.history[self.moves..] {
+            *entry = None;
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.cells.iter().all(|&cell| cell.is_some())
+    }
+    
+    /// Takes back the last move and gives the turn back to the player who made it.
+    /// Returns the move, or None if there is nothing to take back.
+    fn undo(&mut self) -> Option<Coordinate> {
+        let (player, coord) = self.history[self.moves.checked_sub(1)?]?;
+        self.moves -= 1;
+        self.cells[coord.index()] = None;
+        self.turn = player;
+        self.highlight = None;
+        self.termination = None;
+        self.check_if_game_over();
+        Some(coord)
+    }
+
+    /// Plays the last move taken back by `undo` again and passes the turn, like a move made in the game.
+    fn redo(&mut self) -> Option<Coordinate> {
+        let (player, coord) = (*self.history.get(self.moves)?)?;
+        self.cells[coord.index()] = Some(player);
+        self.moves += 1;
+        self.turn = player.opponent();
+        self.highlight = None;
+        self.check_if_game_over();
+        Some(coord)
+    }
+
+    /// Ends the game without a move, e.g. on resignation.
+    fn finish(&mut self, winner: Winner, termination: Termination) {
+        self.highlight = None;
+        self.winner = Some(winner);
+        self.termination = Some(termination);
+    }
+
+    /// The cells that are different on the other board, in reading order.
+    fn diff(&self, other: &Board) -> Vec<CellChange> {
+        (0..9)
+            .filter(|&cell| self.cells[cell] != other.cells[cell])
+            .filter_map(|cell| Some(CellChange { coord: Coordinate::from_index(cell)?, before: self.cells[cell], after: other.cells[cell] }))
+            .collect()
+    }
+
+    /// How many undone moves `redo` can still play.
+    fn redoable(&self) -> usize {
+        self.history[self.moves..].iter().take_while(|entry| entry.is_some()).count()
+    }
+
+    /// The rows, top to bottom.
+    fn rows() -> impl Iterator<Item = Line> {
+        LINES[0..3].iter().copied()
+    }
+
+    /// The columns, left to right.
+    fn columns() -> impl Iterator<Item = Line> {
+        LINES[3..6].iter().copied()
+    }
+
+    /// The diagonal from the top left, then the one from the top right.
+    fn diagonals() -> impl Iterator<Item = Line> {
+        LINES[6..8].iter().copied()
+    }
+
+    /// Every line that wins the game: rows, then columns, then diagonals.
+    fn lines() -> impl Iterator<Item = Line> {
+        Board::rows().chain(Board::columns()).chain(Board::diagonals())
+    }
+
+    /// What the line's cells hold, in the line's order.
+    fn line_cells(&self, line: Line) -> [Option<Player>; 3] {
+        line.map(|cell| self.cells[cell])
+    }
+
+    fn winning_line(&self) -> Option<Line> {
+        // Runs at every node of a search, so this walks the constant table rather than the chained iterators
+        LINES.iter().copied().find(|line| {
+            self.cells[line[0]].is_some() && self.cells[line[0]] == self.cells[line[1]] && self.cells[line[1]] == self.cells[line[2]]
+        })
+    }
+
+    /// Sets the result from the cells. Called after every move once the turn has passed, and after undo
+    /// and redo, so it is also where the `check-invariants` feature checks the board.
+    fn check_if_game_over(&mut self) { 
+        if let Some(pattern) = self.winning_line() {
+            if let Some(player) = self.cells[pattern[0]] {
+                self.winner = Some(Winner::Player(player));
+                self.check_invariants();
+                return;
+            }
+        }
+
+        if self.is_full(){
+            self.winner = Some(Winner::Draw)
+        } else {
+            self.winner = None;
+        }
+        self.check_invariants();
+    }
+
+    /// With the `check-invariants` feature, panics if the board has got into a state no game can reach:
+    /// O has as many marks as X or one more and the turn follows from that, the result matches the
+    /// cells unless the game ended off the board, and the moves in the history are on the board,
+    /// alternating between the players up to the one to move.
+    #[cfg(feature = "check-invariants")]
+    fn check_invariants(&self) {
+        let count = |player| self.cells.iter().filter(|&&cell| cell == Some(player)).count();
+        let (o, x) = (count(Player::O), count(Player::X));
+        assert!(o == x || o == x + 1, "{} has {} O and {} X marks", self.to_compact_string(), o, x);
+        let turn = if o == x { Player::O } else { Player::X };
+        assert!(self.turn == turn, "{} should be {} to move", self.to_compact_string(), turn);
+
+        if self.termination.is_none() {
+            let line = self.winning_line().and_then(|line| self.cells[line[0]]);
+            let winner = line.map(Winner::Player).or(if self.is_full() { Some(Winner::Draw) } else { None });
+            assert!(self.winner == winner, "{} has result {:?} instead of {:?}", self.to_compact_string(), self.winner, winner);
+        }
+
+        let mut player = self.turn;
+        for (number, entry) in self.history[..self.moves].iter().enumerate().rev() {
+            player = player.opponent();
+            let Some((mover, coord)) = *entry else {
+                panic!("{} has no move {} in its history", self.to_compact_string(), number + 1);
+            };
+            assert!(mover == player && self.get_cell(coord) == Some(mover), "{} doesn't match move {} of its history", self.to_compact_string(), number + 1);
+        }
+    }
+
+    #[cfg(not(feature = "check-invariants"))]
+    fn check_invariants(&self) {}
+
+    fn get_cell(&self, coord: Coordinate) -> Option<Player> {
+        self.cells[coord.index()]
+    }
+
+    fn empty_cells(&self) -> Vec<Coordinate> {
+        let mut cells = Vec::new();
+        for row in [Index::Zero, Index::One, Index::Two] {
+            for col in [Index::Zero, Index::One, Index::Two] {
+                let coord = Coordinate { row, col };
+                if self.get_cell(coord).is_none() {
+                    cells.push(coord);
+                }
+            }
+        }
+        cells
+    }
+
+    fn generate_all_equivalent_states(&self) -> Vec<[Option<Player>; 9]> {
+        let mut states = Vec::new();
+        let mut current = self.cells;
+
+        // Add the original state and all its rotations and reflections
+        for _ in 0..2 {
+            for _ in 0..4 {
+                current = rotate_90(current);
+                states.push(current);
+            }
+            current = reflect(current);
+        }
+        
+        states
+    }
+
+    /// The position on one line: rows separated by slashes, then the side to move, e.g. "O.X/.O./... X".
+    /// `from_compact` reads it back into the same cells and turn.
+    pub fn to_compact_string(self) -> String {
+        let key = cells_key(&self.cells);
+        format!("{}/{}/{} {}", &key[0..3], &key[3..6], &key[6..9], self.turn)
+    }
+
+    /// Reads a position written by `to_compact_string`. The side to move may be left out, it then
+    /// follows from the number of marks, and so may the slashes, as in share codes. Positions that can't
+    /// come up in a game are rejected.
+    pub fn from_compact(text: &str) -> Result<Board, ParseError> {
+        let invalid = || ParseError::new(&tr_args("error.position", &[("text", &text)]));
+        let mut parts = text.split_whitespace();
+        let cells = parts.next().ok_or_else(invalid)?;
+        let rows: Vec<&str> = match cells.len() {
+            9 if cells.is_ascii() && !cells.contains('/') => vec![&cells[0..3], &cells[3..6], &cells[6..9]],
+            _ => cells.split('/').collect(),
+        };
+        let turn = parts.next();
+        if rows.len() != 3 || rows.iter().any(|row| row.chars().count() != 3) || parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        let mut board = Board::new();
+        for (index, mark) in rows.concat().chars().enumerate() {
+            board.cells[index] = match mark.to_ascii_uppercase() {
+                'X' => Some(Player::X),
+                'O' => Some(Player::O),
+                '.' => None,
+                _ => return Err(invalid()),
+            };
+        }
+
+        // O moves first, so O has as many marks as X or one more
+        let count = |player| board.cells.iter().filter(|&&cell| cell == Some(player)).count();
+        let expected_turn = match count(Player::O).checked_sub(count(Player::X)) {
+            Some(0) => Player::O,
+            Some(1) => Player::X,
+            _ => return Err(ParseError::new(&tr_args("error.impossible_position", &[("text", &text)]))),
+        };
+        board.turn = match turn.map(str::to_uppercase).as_deref() {
+            None => expected_turn,
+            Some("X") if expected_turn == Player::X => Player::X,
+            Some("O") if expected_turn == Player::O => Player::O,
+            Some("X") | Some("O") => return Err(ParseError::new(&tr_args("error.impossible_position", &[("text", &text)]))),
+            Some(_) => return Err(invalid()),
+        };
+        board.check_if_game_over();
+        Ok(board)
+    }
+
+    /// The same key for every rotation and reflection of a position, e.g. "......XO.".
+    fn canonical_key(&self) -> String {
+        self.generate_all_equivalent_states().iter().map(cells_key).min().unwrap_or_default()
+    }
+
+}
+
+/// Three cells in a row, as indices into `Board::cells`:
+///
+/// ```text
+/// 0 | 1 | 2
+/// 3 | 4 | 5
+/// 6 | 7 | 8
+/// ```
+type Line = [usize; 3];
+
+/// Every line, in the order `Board::lines` yields them.
+const LINES: [Line; 8] = [
+    // Rows
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    // Columns
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    // Diagonals
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cells.hash(state);
+    }
+}
+
+fn cells_key(cells: &[Option<Player>; 9]) -> String {
+    cells.iter().map(|cell| cell.map_or('.', |player| if player == Player::X { 'X' } else { 'O' })).collect()
+}
+
+fn rotate_90(board: [Option<Player>; 9]) -> [Option<Player>; 9] {
+    [
+        board[6], board[3], board[0],
+        board[7], board[4], board[1],
+        board[8], board[5], board[2],
+    ]
+}
+
+fn reflect(board: [Option<Player>; 9]) -> [Option<Player>; 9] {
+    [
+        board[2], board[1], board[0],
+        board[5], board[4], board[3],
+        board[8], board[7], board[6],
+    ]
+}
+
+
+#[repr(u8)]
+#[derive(Copy, Clone, PartialEq, Hash, Eq)]
+pub enum Index {
+    Zero,
+    One,
+    Two,
+}
+
+/// From a 0-based row or column.
+impl TryFrom<usize> for Index {
+    type Error = ParseError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Index::Zero),
+            1 => Ok(Index::One),
+            2 => Ok(Index::Two),
+            _ => Err(ParseError::new(tr("error.index_range"))),
+        }
+    }
+}
+
+impl Index {
+    /// The row or column number shown to players, 1 to 3.
+    fn number(&self) -> usize {
+        *self as usize + 1
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Hash, Eq)]
+pub struct Coordinate {
+    row: Index,
+    col: Index
+}
+
+/// From a 0-based (row, column) pair.
+impl TryFrom<(usize, usize)> for Coordinate {
+    type Error = ParseError;
+
+    fn try_from((row, col): (usize, usize)) -> Result<Self, Self::Error> {
+        let row = Index::try_from(row).map_err(|_| ParseError::new(tr("error.row_range")))?;
+        let col = Index::try_from(col).map_err(|_| ParseError::new(tr("error.col_range")))?;
+        Ok(Coordinate { row, col })
+    }
+}
+
+impl Coordinate {
+    /// The cell's position in `Board::cells`, 0-8 row by row.
+    fn index(&self) -> usize {
+        self.row as usize * 3 + self.col as usize
+    }
+
+    /// The coordinate of a cell index, 0-8 row by row.
+    fn from_index(index: usize) -> Option<Coordinate> {
+        Coordinate::try_from((index / 3, index % 3)).ok()
+    }
+}
+
+
+/// The form players see and type, e.g. "1-3" for the top right corner.
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.row, self.col)
+    }
+}
+
+/// Shows the number players use for the row or column, counting from 1.
+impl Display for Index {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+
+
+
+/// Lines typed on stdin, read on a background thread so that waiting for input can time out.
+fn console_lines() -> &'static Mutex<Receiver<io::Result<String>>> {
+    static LINES: OnceLock<Mutex<Receiver<io::Result<String>>>> = OnceLock::new();
+    LINES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut line = String::new();
+            let result = io::stdin().read_line(&mut line).map(|_| line);
+            let finished = !matches!(&result, Ok(line) if !line.is_empty());
+            if sender.send(result).is_err() || finished {
+                break;
+            }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+fn get_input_from_console() -> Result<String, IoError> {
+    get_input_before(None)?.ok_or(IoError::new(tr("error.read_line")))
+}
+
+/// Waits for a line of input until the deadline, returning None if it passes first.
+fn get_input_before(deadline: Option<Instant>) -> Result<Option<String>, IoError> {
+    let read_error = || IoError::new(tr("error.read_line"));
+    let lines = console_lines().lock().map_err(|_| read_error())?;
+
+    let line = match deadline {
+        Some(deadline) => match lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => return Ok(None),
+            Err(RecvTimeoutError::Disconnected) => return Err(read_error()),
+        },
+        None => lines.recv().map_err(|_| read_error())?,
+    };
+
+    let line = line.map_err(|_| read_error())?;
+    transcript::record_input(&line);
+    Ok(Some(line))
+}
+
+/// Reads a move as players type it, see `coordinates`. Errors about the row or the column point at it
+/// in the trimmed input.
+fn parse_coordinates(input: String) -> Result<Coordinate, ParseError> {
+    coordinates::parse(&input, coordinates::digit_layout())
+}
+
+/// Reads and carries out one line of the player's input. Returns what became of their turn, or an error
+/// only if the console can't be read; anything wrong with the input itself is shown to them.
+fn get_and_play_user_move(board: &mut Board, deadline: Option<Instant>, opponent_is_human: bool) -> Result<Outcome, IoError> {
+    // Prompt the user for their turn if no cell is highlighted
+    if board.highlight.is_none() {
+        say(tr_args("prompt.your_turn", &[("player", &board.turn.label())]));
+        bell::ring();
+    }
+
+    // Get user input from the console
+    let my_input = match get_input_before(deadline)? {
+        Some(input) => input,
+        // Out of time, the caller decides what happens next.
+        None => return Ok(Outcome::Waiting),
+    };
+
+    let result = Command::parse(&my_input).map_err(GameError::from).and_then(|command| commands::execute(board, command, opponent_is_human));
+    result.or_else(|e| {
+        bell::ring();
+        feedback::show(board, &my_input, &e);
+        Ok(Outcome::Waiting)
+    })
+}
+
+
+/// Settings and bookkeeping shared by every node of one search.
+struct Search {
+    ai_player: Player,
+    /// Positions looked at so far.
+    counter: usize,
+    /// Plies after which the search stops looking deeper and scores the position as even.
+    max_depth: Option<i32>,
+    deadline: Option<Instant>,
+    /// Set from another thread to give up the search.
+    cancel: Option<Arc<AtomicBool>>,
+    /// Set once the deadline has passed or the search was cancelled, after which the scores can't be trusted.
+    stopped: bool,
+}
+
+impl Search {
+    fn new(ai_player: Player) -> Search {
+        Search { ai_player, counter: 0, max_depth: None, deadline: None, cancel: None, stopped: false }
+    }
+}
+
+fn ai_best_move(board: &Board, ai_character: Player) -> (Coordinate, usize) {
+    let mut search = Search::new(ai_character);
+    let best_move = search_best_move(board, &mut search);
+    (best_move, search.counter)
+}
+
+/// Like `ai_best_move`, but gives up as soon as the flag is set, returning None.
+fn cancellable_best_move(board: &Board, ai_character: Player, cancel: &Arc<AtomicBool>) -> Option<(Coordinate, usize)> {
+    let mut search = Search { cancel: Some(Arc::clone(cancel)), ..Search::new(ai_character) };
+    let best_move = search_best_move(board, &mut search);
+    (!search.stopped).then_some((best_move, search.counter))
+}
+
+/// Searches at most `max_depth` plies deep. With a time budget it deepens one ply at a time
+/// and keeps the result of the deepest search that finished in time.
+fn limited_best_move(board: &Board, ai_character: Player, max_depth: Option<i32>, time_budget: Option<Duration>) -> (Coordinate, usize) {
+    let Some(budget) = time_budget else {
+        let mut search = Search { max_depth, ..Search::new(ai_character) };
+        let best_move = search_best_move(board, &mut search);
+        return (best_move, search.counter);
+    };
+
+    let deadline = Instant::now() + budget;
+    let mut best_move = board.empty_cells().first().copied().unwrap_or(Coordinate { row: Index::Zero, col: Index::Zero });
+    let mut counter: usize = 0;
+    for depth in 1..=max_depth.unwrap_or(9) {
+        let mut search = Search { max_depth: Some(depth), deadline: Some(deadline), ..Search::new(ai_character) };
+        let found = search_best_move(board, &mut search);
+        counter += search.counter;
+        if search.stopped {
+            break;
+        }
+        best_move = found;
+    }
+    (best_move, counter)
+}
+
+/// Searches on a copy, so the caller's board keeps its redo history, highlight and result.
+fn search_best_move(board: &Board, search: &mut Search) -> Coordinate {
+    let mut position = *board;
+    let board = &mut position;
+    let mut best_move: Coordinate = Coordinate { row: Index::Zero, col: Index::Zero };
+    let mut best_score = i32::MIN;
+    let mut best_traps: usize = 0;
+    let mut seen_states = HashSet::new();
+
+    for row in [Index::Zero, Index::One, Index::Two] {
+        for col in [Index::Zero, Index::One, Index::Two] {
+            let coord: Coordinate = Coordinate { row, col };
+            if board.get_cell(coord).is_some() {
+                continue; // Skip non-empty cells
+            }
+
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+
+            board.check_if_game_over();
+            let state = board.generate_all_equivalent_states();
+
+            if seen_states.insert(state) {
+                let score = min_max(board, false, 0, i32::MIN, i32::MAX, search);
+
+                // Break ties between equally scored moves by how many ways the opponent can go wrong
+                if score >= best_score {
+                    let traps = losing_replies(board, search);
+                    if score > best_score || traps > best_traps {
+                        best_score = score;
+                        best_traps = traps;
+                        best_move = Coordinate { row, col };
+                    }
+                }
+            }
+
+            board.undo();
+        }
+    }
+
+    best_move
+}
+
+/// Counts the opponent's replies after which the bot can force a win. Against a perfect
+/// opponent this changes nothing, against a human it picks the line with the most chances to err.
+fn losing_replies(board: &mut Board, search: &mut Search) -> usize {
+    if board.winner.is_some() {
+        return 0;
+    }
+
+    let mut losing = 0;
+    for coord in board.empty_cells() {
+        board.play_move(coord);
+        board.turn = board.turn.opponent();
+        board.check_if_game_over();
+
+        if min_max(board, true, 1, i32::MIN, i32::MAX, search) > 0 {
+            losing += 1;
+        }
+
+        board.undo();
+    }
+    losing
+}
+
+fn min_max(board: &mut Board, maximizing: bool, depth: i32, mut alpha: i32, mut beta: i32, search: &mut Search) -> i32 {
+    search.counter += 1; // Increment the position counter
+
+    if let Some(winner) = &board.winner {
+        return match winner {
+            Winner::Player(p) => {
+                if *p == search.ai_player { 10 - depth } else { depth - 10 }
+            }
+            Winner::Draw => 0,
+        };
+    }
+
+    if search.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+        return 0;
+    }
+    let cancelled = search.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed));
+    if cancelled || search.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+        search.stopped = true;
+        return 0;
+    }
+
+    let mut best_score = if maximizing { i32::MIN } else { i32::MAX };
+
+    for row in [Index::Zero, Index::One, Index::Two] {
+        for col in [Index::Zero, Index::One, Index::Two] {
+            let coord: Coordinate = Coordinate { row, col };
+            if board.get_cell(coord).is_some() {
+                continue;
+            }
+
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+
+            board.check_if_game_over();
+            let score = min_max(board, !maximizing, depth + 1, alpha, beta, search);
+
+            board.undo();
+
+            if maximizing {
+                best_score = best_score.max(score);
+                alpha = alpha.max(score);
+            } else {
+                best_score = best_score.min(score);
+                beta = beta.min(score);
+            }
+
+            if beta <= alpha {
+                break; // Alpha-beta pruning
+            }
+        }
+    }
+
+    best_score
+}
+
+/// Minimax score of the position from the player's point of view: positive if they can force a win,
+/// zero for a draw, negative if they are lost.
+fn evaluate(board: &Board, player: Player) -> i32 {
+    let mut position = *board;
+    position.check_if_game_over();
+    let maximizing = position.turn == player;
+    min_max(&mut position, maximizing, 0, i32::MIN, i32::MAX, &mut Search::new(player))
+}
+
+/// The bot takes a draw whenever it can no longer force a win from the current position.
+fn bot_accepts_draw(board: &Board, ai_character: Player) -> bool {
+    evaluate(board, ai_character) <= 0
+}
+
+/// Picks, among the moves with the best minimax outcome, the one leading to the position
+/// where the opponent has blundered most often in past games.
+fn exploiter_move(board: &Board, ai_character: Player, model: &OpponentModel) -> (Coordinate, usize) {
+    let mut position = *board;
+    let board = &mut position;
+    let mut search = Search::new(ai_character);
+    let mut candidates: Vec<(Coordinate, i32, f64)> = Vec::new();
+
+    for coord in board.empty_cells() {
+        board.play_move(coord);
+        board.turn = board.turn.opponent();
+        board.check_if_game_over();
+
+        let score = min_max(board, false, 0, i32::MIN, i32::MAX, &mut search);
+        candidates.push((coord, score.signum(), model.blunder_rate(&board.canonical_key())));
+
+        board.undo();
+    }
+
+    let best_outcome = candidates.iter().map(|&(_, outcome, _)| outcome).max().unwrap_or(0);
+    let best_move = candidates
+        .iter()
+        .filter(|&&(_, outcome, _)| outcome == best_outcome)
+        .fold(None, |best: Option<(Coordinate, f64)>, &(coord, _, rate)| match best {
+            Some((_, best_rate)) if best_rate >= rate => best,
+            _ => Some((coord, rate)),
+        })
+        .map(|(coord, _)| coord)
+        .unwrap_or(Coordinate { row: Index::Zero, col: Index::Zero });
+
+    (best_move, search.counter)
+}
+
+fn random_move(board: &Board, rng: &mut impl Rng) -> Option<Coordinate> {
+    board.empty_cells().choose(rng).copied()
+}
+
+/// Everything that decides how the bot plays.
+struct Bot {
+    player: Player,
+    /// Name of the agent configuration the bot was built from.
+    name: String,
+    /// Chance of playing a random move instead of searching. None until the profile sets it, for adaptive bots.
+    blunder_rate: Option<f64>,
+    strategy: Strategy,
+    /// How often the expectimax strategy assumes the opponent plays at random.
+    epsilon: f64,
+    /// How many plies the minimax strategy looks ahead. None searches to the end of the game.
+    max_depth: Option<i32>,
+    /// Time the minimax strategy may spend on each move.
+    time_budget: Option<Duration>,
+    /// Play the opening from the book.
+    book: bool,
+    /// Rules holding back the bot's first move.
+    handicaps: Vec<Handicap>,
+    /// Source of the bot's random choices, seeded when its games should be repeatable.
+    rng: StdRng,
+    /// The human's past choices, used by the exploiter strategy.
+    model: Option<OpponentModel>,
+    /// Weights for the neural strategy.
+    network: Option<Network>,
+    /// Searches ahead during the human's turn, for the minimax strategy.
+    ponderer: Option<Ponderer>,
+}
+
+impl Bot {
+    /// Builds a bot from an agent configuration, loading its network if it needs one.
+    fn new(player: Player, agent: &Agent) -> Result<Bot, GameError> {
+        let network = match agent.strategy {
+            Strategy::Neural => Some(Network::load(agent.weights.as_deref().ok_or(ParseError::new(tr("error.missing_weights")))?)?),
+            _ => None,
+        };
+        Ok(Bot {
+            player,
+            name: agent.name.clone(),
+            blunder_rate: agent.blunder_rate,
+            strategy: agent.strategy,
+            epsilon: agent.epsilon,
+            max_depth: agent.max_depth,
+            time_budget: agent.time_budget,
+            book: agent.book,
+            handicaps: agent.handicaps.clone(),
+            rng: seeded_rng(agent.seed),
+            model: None,
+            network,
+            ponderer: None,
+        })
+    }
+}
+
+/// How the bot came up with its move.
+#[derive(PartialEq, Clone, Copy)]
+enum MoveSource {
+    /// Searched, with the number of positions looked at.
+    Search(usize),
+    Book,
+    Blunder,
+    Handicap,
+}
+
+/// Picks the bot's move without playing it.
+fn choose_bot_move(board: &mut Board, bot: &mut Bot) -> (Coordinate, MoveSource) {
+    let player = bot.player;
+    if let Some(coord) = handicap_move(board, player, &bot.handicaps, &mut bot.rng) {
+        return (coord, MoveSource::Handicap);
+    }
+    if bot.rng.gen::<f64>() < bot.blunder_rate.unwrap_or(0.0) {
+        if let Some(blunder) = random_move(board, &mut bot.rng) {
+            return (blunder, MoveSource::Blunder);
+        }
+    }
+    if bot.book {
+        if let Some(coord) = book::book_move(board) {
+            return (coord, MoveSource::Book);
+        }
+    }
+
+    let (best_move, counter): (Coordinate, usize) = match (bot.strategy, &bot.model) {
+        (Strategy::Exploiter, Some(model)) => exploiter_move(board, player, model),
+        (Strategy::Neural, _) => match bot.network.as_ref().and_then(|network| network.best_move(board)) {
+            // The network scores the position and each reply to it
+            Some(coord) => (coord, board.empty_cells().len() + 1),
+            None => ai_best_move(board, player),
+        },
+        (Strategy::Expectimax, _) => {
+            expectimax::best_move(board, player, bot.epsilon).unwrap_or_else(|| ai_best_move(board, player))
+        }
+        _ if bot.max_depth.is_some() || bot.time_budget.is_some() => {
+            limited_best_move(board, player, bot.max_depth, bot.time_budget)
+        }
+        _ => match bot.ponderer.as_ref().and_then(|ponderer| ponderer.lookup(board)) {
+            Some(pondered) => pondered,
+            None => ai_best_move(board, player),
+        },
+    };
+    (best_move, MoveSource::Search(counter))
+}
+
+/// Chooses and plays the bot's move, taking at least `delay` over it with a spinner so that the reply doesn't
+/// come instantly. A pondering bot starts on the replies to its move in the meantime. Returns how the move
+/// was found and how long was spent waiting on top of choosing it.
+fn play_bot_move(board: &mut Board, bot: &mut Bot, delay: Duration) -> (MoveSource, Duration) {
+    let started = Instant::now();
+    let (coord, source) = choose_bot_move(board, bot);
+    let pause = delay.saturating_sub(started.elapsed());
+    if !pause.is_zero() {
+        let mut after = *board;
+        generate::play(&mut after, coord);
+        if let Some(ponderer) = bot.ponderer.as_mut().filter(|_| after.winner.is_none()) {
+            ponderer.start(&after, bot.player);
+        }
+        thinking_pause(pause, board.style);
+    }
+    board.play_move(coord);
+    match source {
+        MoveSource::Search(count) => say(tr_args("bot.report", &[("count", &count), ("move", &coord)])),
+        MoveSource::Book => say(tr_args("bot.book", &[("move", &coord)])),
+        MoveSource::Blunder => say(tr_args("bot.blunder", &[("move", &coord)])),
+        MoveSource::Handicap => say(tr_args("bot.handicap", &[("move", &coord)])),
+    }
+    (source, pause)
+}
+
+/// Waits, turning a spinner after "Thinking" on the boards drawn for people to look at.
+fn thinking_pause(pause: Duration, style: BoardStyle) {
+    if !matches!(style, BoardStyle::Pretty | BoardStyle::Ascii) {
+        thread::sleep(pause);
+        return;
+    }
+    let end = Instant::now() + pause;
+    for frame in ['|', '/', '-', '\\'].iter().cycle() {
+        let left = end.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            break;
+        }
+        print!("\r{} {}", tr("bot.thinking"), frame);
+        let _ = io::stdout().flush();
+        thread::sleep(left.min(Duration::from_millis(100)));
+    }
+    print!("\r{}\r", " ".repeat(tr("bot.thinking").chars().count() + 2));
+}
+
+/// Random numbers from a fixed seed, so a run can be replayed, or from the OS without one.
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Gives an agent without a seed of its own one derived from `--seed`, different for each side
+/// so that two bots don't share one sequence of random numbers.
+fn bot_seed(agent: &Agent, player: Player, seed: Option<u64>) -> Agent {
+    Agent { seed: agent.seed.or(seed.map(|seed| seed.wrapping_add(player as u64 + 1))), ..agent.clone() }
+}
+
+/// Seats the bots. Without `--p1`/`--p2` the human picks a side and the bot from the global options
+/// takes the other one. Bots are indexed by the side they play.
+/// A lone human plays the given side if there is one, as when resuming a paused game, or else the side
+/// from the options unless a coin is to be tossed for it.
+fn seat_bots(options: &Options, side: Option<Player>, rng: &mut impl Rng) -> Result<[Option<Bot>; 2], GameError> {
+    let mut bots = [None, None];
+    if options.p1.is_none() && options.p2.is_none() {
+        // Build the bot before any prompts, so a bad weights file fails right away
+        let mut bot = Bot::new(Player::X, &bot_seed(&Agent::from_options(options), Player::X, options.seed))?;
+        let side = match side.or(options.side.filter(|_| !options.random_side)) {
+            Some(side) => side,
+            None if options.random_side => coin_toss(rng),
+            None => pick_side()?,
+        };
+        bot.player = side.opponent();
+        bots[side.opponent() as usize] = Some(bot);
+    } else {
+        for (player, seat) in [(Player::O, &options.p1), (Player::X, &options.p2)] {
+            if let Some(Seat::Bot(agent)) = seat {
+                bots[player as usize] = Some(Bot::new(player, &bot_seed(agent, player, options.seed))?);
+            }
+        }
+    }
+    Ok(bots)
+}
+
+/// Loads the named profile, or shows why it couldn't be.
+fn load_profile(name: &str) -> Option<Profile> {
+    match Profile::load(name) {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            say(tr_args("error.prefix", &[("message", &e)]));
+            None
+        }
+    }
+}
+
+/// Asks the human which side to play.
+fn pick_side() -> Result<Player, GameError> {
+    say(tr("prompt.pick_side"));
+    let user_input: String = get_input_from_console()?.trim().to_lowercase();
+    
+    match user_input.as_str() {
+        "x" => Ok(Player::X),
+        "o" => Ok(Player::O),
+        _ => Err(ParseError::new(tr("error.invalid_side")).into()),
+    }
+}
+
+/// Flips a coin for the human's side, with a short pause before the reveal.
+fn coin_toss(rng: &mut impl Rng) -> Player {
+    print!("{}", tr("coin.flipping"));
+    for _ in 0..3 {
+        let _ = io::stdout().flush();
+        thread::sleep(Duration::from_millis(400));
+        print!(".");
+    }
+    println!();
+    // Printed a dot at a time above, so recorded as the whole line once it is done
+    transcript::record(&format!("{}...", tr("coin.flipping")));
+
+    let side = if rng.gen::<bool>() { Player::O } else { Player::X };
+    say(tr_args("coin.result", &[("side", &side)]));
+    thread::sleep(Duration::from_millis(800));
+    side
+}
+
+/// Asks the human for a name. An empty answer keeps the plain mark.
+/// With two humans at the keyboard the prompt says which side is being named.
+fn ask_name(side: Option<Player>) -> Result<Option<String>, IoError> {
+    match side {
+        Some(side) => say(tr_args("prompt.name_for", &[("side", &side)])),
+        None => say(tr("prompt.name")),
+    }
+    let name = get_input_from_console()?.trim().to_string();
+    Ok(if name.is_empty() { None } else { Some(name) })
+}
+
+/// Prints a line of the game, and adds it to the transcript if one is being recorded.
+fn say(text: impl Display) {
+    let text = text.to_string();
+    println!("{}", text);
+    transcript::record(&text);
+}
+
+fn clear_screen() {
+    print!("\x1B[2J\x1B[1;1H");
+}
+
+/// Flags the player to move: a loss normally, a random move on a casual clock.
+fn play_out_of_time(board: &mut Board, casual: bool, rng: &mut impl Rng) {
+    say(tr_args("clock.out_of_time", &[("player", &board.turn.name())]));
+    board.highlight = None;
+
+    match random_move(board, rng) {
+        Some(coord) if casual => {
+            say(tr_args("clock.random_move", &[("move", &coord)]));
+            board.play_move(coord);
+        }
+        _ => board.finish(Winner::Player(board.turn.opponent()), Termination::Timeout),
+    }
+}
+
+/// Reads commands until the player moves, pauses the game, runs out of time, or idles past the timeout twice.
+/// The first idle timeout only warns, the second forfeits the game to the opponent.
+fn play_human_turn(board: &mut Board, clock: &Clock, idle_timeout: Option<Duration>, opponent_is_human: bool, rng: &mut impl Rng) -> Outcome {
+    let mut idle_deadline = idle_timeout.map(|timeout| Instant::now() + timeout);
+    let mut warned = false;
+
+    loop {
+        let deadline = match (clock.deadline(), idle_deadline) {
+            (Some(clock_deadline), Some(idle_deadline)) => Some(clock_deadline.min(idle_deadline)),
+            (clock_deadline, idle_deadline) => clock_deadline.or(idle_deadline),
+        };
+
+        match get_and_play_user_move(board, deadline, opponent_is_human) {
+            Ok(Outcome::Waiting) => {}
+            Ok(outcome) => return outcome,
+            // With the console gone no move will ever come, so the player is out of the game
+            Err(e) => {
+                say(tr_args("error.prefix", &[("message", &e)]));
+                say(tr_args("input.closed", &[("player", &board.turn.name())]));
+                board.finish(Winner::Player(board.turn.opponent()), Termination::Resignation);
+                return Outcome::Played;
+            }
+        }
+
+        if clock.is_expired() {
+            play_out_of_time(board, clock.casual, rng);
+            return Outcome::Played;
+        }
+
+        if let (Some(timeout), Some(deadline)) = (idle_timeout, idle_deadline) {
+            if Instant::now() < deadline {
+                // The player typed something, so they are still here.
+                warned = false;
+            } else if !warned {
+                say(tr_args("idle.warning", &[("player", &board.turn.name()), ("seconds", &timeout.as_secs())]));
+                warned = true;
+            } else {
+                say(tr_args("idle.forfeit", &[("player", &board.turn.name())]));
+                board.finish(Winner::Player(board.turn.opponent()), Termination::Timeout);
+                return Outcome::Played;
+            }
+            idle_deadline = Some(Instant::now() + timeout);
+        }
+    }
+}
+
+/// A command run from the command line, given the arguments after its name.
+type Subcommand = fn(&[String]) -> Result<(), GameError>;
+
+/// Runs the game, or the command given, with the arguments the program was started with.
+pub fn run() {
+    i18n::set_language(i18n::language_from_env());
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Everything but playing a game is a command named first, run with the arguments after it
+    let command: Option<Subcommand> = match args.first().map(String::as_str) {
+        Some("--version") => Some(info::run),
+        Some("selfplay") => Some(selfplay::run),
+        Some("swiss") => Some(tournament::run_swiss),
+        Some("gauntlet") => Some(tournament::run_gauntlet),
+        Some("replay") => Some(replay::run),
+        Some("games") => Some(archive::run),
+        Some("stats") => Some(stats::run),
+        Some("leaderboard") => Some(stats::run_leaderboard),
+        Some("correspond") => Some(correspond::run),
+        Some("practice") => Some(practice::run),
+        Some("puzzles") => Some(puzzles::run),
+        Some("count-positions") => Some(positions::run),
+        Some("strategy-table") => Some(positions::run_strategy_table),
+        Some("export-svg") => Some(svg::run),
+        Some("share") => Some(share::run),
+        Some("irc") => Some(irc::run),
+        _ => None,
+    };
+    if let Some(command) = command {
+        if let Err(e) = command(&args[1..]) {
+            say(tr_args("error.prefix", &[("message", &e)]));
+            // A failing command exits with an error, so that the script or CI job running it fails too,
+            // e.g. on a regressed gauntlet or a tampered archive
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // A quick game asks nothing and keeps nothing: built-in settings, a random side, no profile, nothing saved
+    let quick = args.first().map(String::as_str) == Some("quick");
+    let args = if quick { args[1..].to_vec() } else { args };
+
+    // A paused game is set up again from the arguments it was started with
+    let resumed = if quick { None } else { autosave::offer_resume() };
+    let args = resumed.as_ref().map_or(args, |autosave| autosave.session.args.clone());
+
+    let config = if quick { Ok(Config::default()) } else { Config::load() };
+    let (mut options, mut config) = match config.and_then(|config| Ok((Options::new(&args, &config)?, config))) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            say(tr_args("error.prefix", &[("message", &e)]));
+            return;
+        }
+    };
+
+    i18n::set_language(options.language);
+    if let Some(path) = &options.transcript {
+        if let Err(e) = transcript::start(path) {
+            say(tr_args("error.prefix", &[("message", &e)]));
+            return;
+        }
+    }
+
+    // A lone human playing a bot is named first, so that their profile can fill in the settings they didn't give
+    let bot_seats = [&options.p1, &options.p2].into_iter().filter(|seat| matches!(seat, Some(Seat::Bot(_)))).count();
+    let lone_human = (options.p1.is_none() && options.p2.is_none()) || bot_seats == 1;
+    let human_name = match (&resumed, lone_human) {
+        (_, false) => None,
+        (Some(autosave), true) => autosave.session.human.and_then(|side| autosave.session.names[side as usize].clone()),
+        (None, true) if quick => options.name.clone(),
+        (None, true) => options.name.clone().or_else(|| ask_name(None).unwrap_or(None)),
+    };
+    let mut profile = human_name.as_deref().filter(|_| !quick).and_then(load_profile);
+    if let Some(profile) = &profile {
+        profile.apply(&mut config);
+        options = match Options::new(&args, &config) {
+            Ok(options) => options,
+            Err(e) => {
+                say(tr_args("error.prefix", &[("message", &e)]));
+                return;
+            }
+        };
+        if let Some(greeting) = profile.greeting() {
+            say(greeting);
+        }
+    }
+
+    coordinates::set_digit_layout(options.digits);
+    bell::set_enabled(options.bell);
+
+    let mut game = match options.position.as_deref().map(Board::from_compact).unwrap_or_else(|| Ok(Board::new())) {
+        Ok(board) => board,
+        Err(e) => {
+            say(tr_args("error.prefix", &[("message", &e)]));
+            return;
+        }
+    };
+    game.theme = options.theme;
+    game.style = options.board_style;
+    game.scale = options.scale;
+    game.numbers = options.numbers;
+    game.flip = options.flip;
+    let mut clock = Clock::new(options.clock, options.move_time, options.casual_clock);
+
+    // Seat the bots, asking for the human's side if there is just one bot
+    // A run without --seed gets one of its own, so that a report can repeat it
+    let seed = *options.seed.get_or_insert_with(rand::random);
+    report::start(args.clone(), seed);
+    // Everything random outside the bots' own choices, seeded by --seed
+    let mut rng = seeded_rng(options.seed);
+
+    let side = match &resumed {
+        Some(autosave) => autosave.session.human,
+        // Only a human playing the bot has a side to toss for, the seats given with --p1 and --p2 are fixed
+        None if quick && options.p1.is_none() && options.p2.is_none() => {
+            let side = if rng.gen::<bool>() { Player::O } else { Player::X };
+            say(tr_args("coin.result", &[("side", &side)]));
+            Some(side)
+        }
+        None => None,
+    };
+    let mut bots = match seat_bots(&options, side, &mut rng) {
+        Ok(bots) => bots,
+        Err(e) => {
+            say(tr_args("error.prefix", &[("message", &e)]));
+            return;
+        }
+    };
+    let humans: Vec<Player> = [Player::O, Player::X].into_iter().filter(|side| bots[*side as usize].is_none()).collect();
+    // Profiles, models and results are kept for a lone human playing a bot
+    let human = match humans[..] {
+        [side] => Some(side),
+        _ => None,
+    };
+
+    // Name both sides, asking humans only if no name was configured
+    let mut names = [None, None];
+    for side in [Player::O, Player::X] {
+        let resumed_name = resumed.as_ref().map(|autosave| autosave.session.names[side as usize].clone());
+        names[side as usize] = match &bots[side as usize] {
+            Some(_) if human.is_some() => options.bot_name.clone(),
+            Some(bot) => Some(bot.name.clone()),
+            None if human.is_some() => human_name.clone(),
+            None if quick => None,
+            None => resumed_name.unwrap_or_else(|| ask_name(Some(side)).unwrap_or(None)),
+        };
+    }
+    set_player_names(names.clone());
+
+    // Named players keep a profile. Adaptive play needs one, so anonymous players share the guest profile.
+    let mut opponent = human.and_then(|side| bots[side.opponent() as usize].as_mut());
+    let adaptive = opponent.as_ref().is_some_and(|bot| bot.blunder_rate.is_none());
+    if profile.is_none() && adaptive && !quick {
+        profile = load_profile("guest");
+    }
+    if let Some(bot) = opponent.as_mut() {
+        bot.blunder_rate = bot.blunder_rate.or(profile.as_ref().map(|profile| profile.blunder_rate));
+        bot.model = profile.as_ref().and_then(|profile| match OpponentModel::load(&profile.name) {
+            Ok(model) => Some(model),
+            Err(e) => {
+                say(tr_args("error.prefix", &[("message", &e)]));
+                None
+            }
+        });
+        if options.ponder && bot.strategy == Strategy::Minimax {
+            bot.ponderer = Some(Ponderer::default());
+        }
+    }
+
+    // Tutorial comments on the last move, shown under the next board
+    let mut commentary: Vec<String> = Vec::new();
+    // Time spent and positions searched by each side, for the result, and the time each move took
+    let mut time_used = [Duration::ZERO; 2];
+    let mut move_times: [Option<Duration>; 9] = [None; 9];
+    let mut nodes = [0; 2];
+    // For the game archive
+    let start = options.position.as_ref().map(|_| game.to_compact_string());
+    let first_position = game;
+    let began = resumed.as_ref().map_or_else(SystemTime::now, |autosave| autosave.session.began);
+    let mut events = Events::default();
+    events.subscribe(Box::new(bell::Bell));
+    if !quick {
+        autosave::start_session(Session { args, human, names, began });
+        events.subscribe(Box::new(autosave::Saver));
+    }
+
+    // Play the unfinished game's moves again
+    if let Some(autosave) = &resumed {
+        for &coord in &autosave.moves {
+            if game.winner.is_some() || game.get_cell(coord).is_some() {
+                say(tr_args("error.prefix", &[("message", &tr("error.resume"))]));
+                return;
+            }
+            generate::play(&mut game, coord);
+        }
+        game.takebacks = autosave.takebacks;
+        events.emit_changes(&first_position, &game);
+    }
+    // Logged from here on, as the log of a resumed game already holds the moves played again above
+    if !quick {
+        match EventLog::start(&game, began) {
+            Ok(log) => events.subscribe(Box::new(log)),
+            Err(e) => say(tr_args("error.prefix", &[("message", &e)])),
+        }
+    }
+
+    // Main game loop
+    while game.winner.is_none() && !game.is_full() {
+        clear_screen();
+        say(game);
+        if clock.is_enabled() {
+            say(&clock);
+        }
+        for comment in commentary.drain(..) {
+            say(comment);
+        }
+        if options.estimate {
+            say(rollout::estimate(&game, rollout::ROLLOUTS, &mut rng));
+        }
+        if options.tutorial && bots[game.turn as usize].is_none() {
+            if let Some(advice) = tutorial::advise(&game, game.turn) {
+                say(advice);
+            }
+        }
+
+        let before = game;
+        let started = Instant::now();
+        clock.start(game.turn);
+        let (mover, waiting) = (game.turn as usize, game.turn.opponent() as usize);
+        // The bot's pause for show, left out of its time. On a clock it would cost the bot, so there is none.
+        let mut paused = Duration::ZERO;
+        if let Some(bot) = bots[mover].as_mut() {
+            // Bot's turn
+            let delay = if clock.is_enabled() { Duration::ZERO } else { options.think_delay };
+            let (source, pause) = play_bot_move(&mut game, bot, delay);
+            if let MoveSource::Search(count) = source {
+                nodes[mover] += count;
+            }
+            paused = pause;
+        } else {
+            // Player's turn
+            if let Some(bot) = bots[waiting].as_mut() {
+                if let Some(ponderer) = bot.ponderer.as_mut() {
+                    ponderer.start(&game, bot.player);
+                }
+            }
+            let outcome = play_human_turn(&mut game, &clock, options.idle_timeout, bots[waiting].is_none(), &mut rng);
+            if let Some(ponderer) = bots[waiting].as_mut().and_then(|bot| bot.ponderer.as_mut()) {
+                ponderer.stop();
+            }
+            if outcome == Outcome::Paused {
+                clock.stop();
+                match autosave::save(&game) {
+                    Ok(()) => {
+                        clear_screen();
+                        say(tr("pause.saved"));
+                        return;
+                    }
+                    // Not saved, so the game goes on with the same player to move
+                    Err(e) => {
+                        say(tr_args("error.prefix", &[("message", &e)]));
+                        continue;
+                    }
+                }
+            }
+        }
+        clock.stop();
+        if let Some(remaining) = clock.remaining(before.turn) {
+            events.emit(Event::ClockTick { player: before.turn, remaining }, &game);
+        }
+        let took = started.elapsed().saturating_sub(paused);
+        time_used[mover] += took;
+        if !before.diff(&game).is_empty() && game.moves > 0 {
+            move_times[game.moves - 1] = Some(took);
+        }
+
+        if options.tutorial {
+            commentary = tutorial::comment_on_move(&before, &game, game.turn);
+        }
+
+        // Switch turns
+        game.turn = game.turn.opponent();
+
+        // Check if the game is over, unless a resignation or agreed draw already decided it
+        if game.winner.is_none() {
+            game.check_if_game_over();
+        }
+
+        // Remember what the human played here, and whether it threw away a better outcome
+        if let Some(side) = human.filter(|side| before.turn == *side && game.moves == before.moves + 1) {
+            if let Some(model) = bots[side.opponent() as usize].as_mut().and_then(|bot| bot.model.as_mut()) {
+                let blunder = evaluate(&game, side).signum() < evaluate(&before, side).signum();
+                model.record(before.canonical_key(), game.canonical_key(), blunder);
+            }
+        }
+
+        events.emit_changes(&before, &game);
+    }
+
+    // Print final board state
+    say(game);
+    for comment in commentary {
+        say(comment);
+    }
+
+    // Print game result
+    let Some(result) = GameResult::new(&game, time_used, nodes) else {
+        say(tr("result.unexpected"));
+        return;
+    };
+    say(&result);
+    let usual_game = options.p1.is_none() && options.p2.is_none();
+    let saved = SavedGame {
+        difficulty: usual_game.then_some(options.difficulty),
+        times: move_times[..game.moves].to_vec(),
+        ..SavedGame::new(&game, &result, start, human, began)
+    };
+    if let (Some(side), Some(accuracy)) = (human, saved.accuracy) {
+        say(tr_args("result.accuracy", &[("player", &side.name()), ("accuracy", &format!("{:.0}", accuracy))]));
+    }
+    let saved_id = if quick {
+        None
+    } else {
+        match archive::save(saved.clone()) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                say(tr_args("error.prefix", &[("message", &e)]));
+                None
+            }
+        }
+    };
+
+    // Update the human's record
+    let outcome = human.map(|side| result.outcome_for(side));
+    if let (Some(profile), Some(outcome), Some(side)) = (profile.as_mut(), outcome, human) {
+        profile.record(outcome);
+        profile.remember(&options, side);
+        if let Err(e) = profile.save() {
+            say(tr_args("error.prefix", &[("message", &e)]));
+        }
+    }
+    for model in bots.into_iter().flatten().filter_map(|bot| bot.model) {
+        if let Err(e) = model.save() {
+            say(tr_args("error.prefix", &[("message", &e)]));
+        }
+    }
+
+    // Offer to look back over the game to whoever played it
+    if let Some(id) = saved_id.filter(|_| !humans.is_empty()) {
+        say(tr("prompt.explore"));
+        if get_input_from_console().is_ok_and(|answer| commands::is_yes(&answer)) {
+            if let Err(e) = archive::explore_game(&SavedGame { id, ..saved }, &first_position) {
+                say(tr_args("error.prefix", &[("message", &e)]));
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! A small fully connected network that scores positions, loaded from a plain text weights file:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! layer 27 32
+//! <32 lines of 27 weights followed by a bias>
+//! layer 32 10
+//! <10 lines of 32 weights followed by a bias>
+//! ```
+//!
+//! The input is 27 values, three per cell row by row: 1 if the cell holds the mark of the side to move,
+//! 1 if it holds the opponent's mark, 1 if it is empty. Hidden layers use ReLU. The last layer has
+//! 10 outputs: a policy logit per cell, then the value of the position for the side to move in [-1, 1].
+
+use std::fs;
+
+use crate::error::{GameError, IoError, ParseError};
+use crate::i18n::tr_args;
+use crate::{Board, Coordinate, Player};
+
+const INPUTS: usize = 27;
+const OUTPUTS: usize = 10;
+
+struct Layer {
+    inputs: usize,
+    /// One row per output, `inputs` weights followed by the bias.
+    rows: Vec<Vec<f64>>,
+}
+
+pub struct Network {
+    layers: Vec<Layer>,
+}
+
+impl Network {
+    pub fn load(path: &str) -> Result<Network, GameError> {
+        let text = fs::read_to_string(path).map_err(|_| IoError::new(&tr_args("error.read_file", &[("path", &path)])))?;
+        Ok(Network::parse(&text).map_err(|line| ParseError::new(&tr_args("error.weights", &[("path", &path), ("line", &line)])))?)
+    }
+
+    /// Parses a weights file, returning the number of the offending line on failure.
+    fn parse(text: &str) -> Result<Network, usize> {
+        let mut layers: Vec<Layer> = Vec::new();
+        let mut expected_rows = 0;
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields[0] == "layer" {
+                let sizes: Vec<usize> = fields[1..].iter().filter_map(|field| field.parse().ok()).collect();
+                let [inputs, outputs] = sizes[..] else {
+                    return Err(number + 1);
+                };
+                let previous = layers.last().map_or(INPUTS, |layer| layer.rows.len());
+                if expected_rows != 0 || inputs != previous {
+                    return Err(number + 1);
+                }
+                layers.push(Layer { inputs, rows: Vec::with_capacity(outputs) });
+                expected_rows = outputs;
+                continue;
+            }
+
+            let layer = layers.last_mut().ok_or(number + 1)?;
+            let row: Vec<f64> = fields.iter().filter_map(|field| field.parse().ok()).collect();
+            if expected_rows == 0 || row.len() != layer.inputs + 1 {
+                return Err(number + 1);
+            }
+            layer.rows.push(row);
+            expected_rows -= 1;
+        }
+
+        let line_count = text.lines().count();
+        if expected_rows != 0 || layers.last().map(|layer| layer.rows.len()) != Some(OUTPUTS) {
+            return Err(line_count);
+        }
+        Ok(Network { layers })
+    }
+
+    fn forward(&self, board: &Board, to_move: Player) -> Vec<f64> {
+        let mut values: Vec<f64> = board
+            .cells
+            .iter()
+            .flat_map(|cell| match cell {
+                Some(player) if *player == to_move => [1.0, 0.0, 0.0],
+                Some(_) => [0.0, 1.0, 0.0],
+                None => [0.0, 0.0, 1.0],
+            })
+            .collect();
+
+        for (index, layer) in self.layers.iter().enumerate() {
+            let hidden = index + 1 < self.layers.len();
+            values = layer
+                .rows
+                .iter()
+                .map(|row| {
+                    let sum = row[..layer.inputs].iter().zip(&values).map(|(weight, value)| weight * value).sum::<f64>()
+                        + row[layer.inputs];
+                    if hidden { sum.max(0.0) } else { sum }
+                })
+                .collect();
+        }
+        values
+    }
+
+    /// Looks one move ahead: takes an immediate win if there is one, otherwise the move that leaves the
+    /// opponent the worst value, with the policy logits breaking ties.
+    pub fn best_move(&self, board: &Board) -> Option<Coordinate> {
+        let player = board.turn;
+        let policy = self.forward(board, player);
+        let mut best: Option<(usize, f64, f64)> = None;
+
+        for cell in (0..9).filter(|&cell| board.cells[cell].is_none()) {
+            let mut after = *board;
+            after.cells[cell] = Some(player);
+            after.check_if_game_over();
+
+            let score = match after.winner {
+                Some(_) if after.winning_line().is_some() => f64::INFINITY,
+                Some(_) => 0.0,
+                None => -self.forward(&after, player.opponent())[OUTPUTS - 1].tanh(),
+            };
+            let logit = policy[cell];
+            if best.is_none_or(|(_, best_score, best_logit)| score > best_score || (score == best_score && logit > best_logit)) {
+                best = Some((cell, score, logit));
+            }
+        }
+
+        best.and_then(|(cell, _, _)| Coordinate::from_index(cell))
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{data_dir, safe_file_name};
+use crate::error::{IoError, ParseError};
+use crate::i18n::tr_args;
+use crate::save_format;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Choice {
+    times: u32,
+    blunder: bool,
+}
+
+/// The moves a human has chosen in each position, keyed by canonical position so that
+/// rotations and reflections of the same position share their history.
+#[derive(Debug)]
+pub struct OpponentModel {
+    name: String,
+    /// Canonical position before the move -> canonical position after it -> how often it was chosen.
+    choices: HashMap<String, HashMap<String, Choice>>,
+}
+
+impl OpponentModel {
+    /// Loads the model for the named player, starting an empty one if there is none yet. An error if it was
+    /// saved in a newer format.
+    pub fn load(name: &str) -> Result<OpponentModel, ParseError> {
+        let mut model = OpponentModel { name: name.to_string(), choices: HashMap::new() };
+        let Ok(text) = fs::read_to_string(model.path()) else {
+            return Ok(model);
+        };
+        let text = save_format::upgrade(&text, &model.path())?;
+
+        // After the format, one choice per line: position, resulting position, times chosen, and 1 if it was a blunder
+        for line in text.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if let [position, after, times, blunder] = fields[..] {
+                let choice = Choice { times: times.parse().unwrap_or(0), blunder: blunder == "1" };
+                model.choices.entry(position.to_string()).or_default().insert(after.to_string(), choice);
+            }
+        }
+        Ok(model)
+    }
+
+    pub fn save(&self) -> Result<(), IoError> {
+        let mut text = save_format::header();
+        for (position, choices) in &self.choices {
+            for (after, choice) in choices {
+                text.push_str(&format!("{} {} {} {}\n", position, after, choice.times, u8::from(choice.blunder)));
+            }
+        }
+
+        let path = self.path();
+        path.parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| fs::write(&path, text))
+            .map_err(|_| IoError::new(&tr_args("error.save_model", &[("name", &self.name)])))
+    }
+
+    pub fn record(&mut self, position: String, after: String, blunder: bool) {
+        let choice = self.choices.entry(position).or_default().entry(after).or_default();
+        choice.times += 1;
+        choice.blunder = blunder;
+    }
+
+    /// How often the player has blundered in this position, from 0 to 1. Unseen positions count as 0.
+    pub fn blunder_rate(&self, position: &str) -> f64 {
+        let Some(choices) = self.choices.get(position) else {
+            return 0.0;
+        };
+        let total: u32 = choices.values().map(|choice| choice.times).sum();
+        let blunders: u32 = choices.values().filter(|choice| choice.blunder).map(|choice| choice.times).sum();
+        if total == 0 { 0.0 } else { f64::from(blunders) / f64::from(total) }
+    }
+
+    fn path(&self) -> PathBuf {
+        data_dir().join("models").join(safe_file_name(&self.name))
+    }
+}
@@ -0,0 +1,342 @@
+use std::time::Duration;
+
+use crate::agent::{self, Seat};
+use crate::archive::parse_player;
+use crate::config::Config;
+use crate::coordinates::DigitLayout;
+use crate::difficulty::Difficulty;
+use crate::error::ParseError;
+use crate::handicap::Handicap;
+use crate::i18n::{self, tr, tr_args, Language};
+use crate::render::{self, BoardStyle, CellNumbers, Flip};
+use crate::strategy::Strategy;
+use crate::theme::Theme;
+use crate::Player;
+
+/// How long the bot takes over a move at least, unless `--think-delay` says otherwise.
+const DEFAULT_THINK_DELAY: Duration = Duration::from_millis(500);
+
+/// Settings for a single run, built from the config file and then overridden by command line flags.
+#[derive(Debug)]
+pub struct Options {
+    pub theme: Theme,
+    /// How the board is drawn.
+    pub board_style: BoardStyle,
+    /// How big the grids draw the cells, with big marks above 1.
+    pub scale: usize,
+    /// What to write in empty cells to show what to type there.
+    pub numbers: CellNumbers,
+    /// How the grids turn the board for the eye.
+    pub flip: Flip,
+    pub language: Language,
+    /// Time bank per player for the whole game.
+    pub clock: Option<Duration>,
+    /// Time limit for each individual move.
+    pub move_time: Option<Duration>,
+    pub casual_clock: bool,
+    /// How long a player may go without typing anything before being warned, and then forfeiting.
+    pub idle_timeout: Option<Duration>,
+    /// Name of the human player. Asked for at startup when not set.
+    pub name: Option<String>,
+    pub bot_name: Option<String>,
+    /// The human's side against the bot. Asked for at startup when not set.
+    pub side: Option<Player>,
+    /// Toss a coin for the human's side instead of asking.
+    pub random_side: bool,
+    pub difficulty: Difficulty,
+    /// Narrate strategy and point out threats as the game goes.
+    pub tutorial: bool,
+    /// Show the odds of each result from random play every turn.
+    pub estimate: bool,
+    /// Ring the terminal bell on the human's turn, on a move that can't be played and when the game is won.
+    pub bell: bool,
+    /// File to append a record of the session to, boards, prompts and what was typed.
+    pub transcript: Option<String>,
+    pub strategy: Strategy,
+    /// For the expectimax AI: how often the opponent is assumed to play a random move.
+    pub epsilon: f64,
+    /// Weights file for the neural AI.
+    pub weights: Option<String>,
+    /// Let the minimax AI think ahead while the human is typing.
+    pub ponder: bool,
+    /// The least time the bot takes over a move, so that its replies don't come instantly. Zero for none.
+    pub think_delay: Duration,
+    /// Who plays O, the side that moves first. Set together with `p2` for games without the usual human and bot.
+    pub p1: Option<Seat>,
+    /// Who plays X.
+    pub p2: Option<Seat>,
+    /// Position to start from instead of the empty board, in the compact one-line form.
+    pub position: Option<String>,
+    /// Seed for every random choice in the game, to make a run repeatable.
+    pub seed: Option<u64>,
+    /// Rules holding back the first move of the bot, for beginners.
+    pub handicaps: Vec<Handicap>,
+    /// How single digits typed as moves name the cells.
+    pub digits: DigitLayout,
+}
+
+impl Options {
+    pub fn new(args: &[String], config: &Config) -> Result<Options, ParseError> {
+        let mut options = Options {
+            theme: Theme::default(),
+            board_style: BoardStyle::default(),
+            scale: 1,
+            numbers: CellNumbers::default(),
+            flip: Flip::default(),
+            language: i18n::language_from_env(),
+            clock: None,
+            move_time: None,
+            casual_clock: false,
+            idle_timeout: None,
+            name: None,
+            bot_name: None,
+            side: None,
+            random_side: config.get("random_side") == Some("true"),
+            difficulty: Difficulty::default(),
+            tutorial: config.get("tutorial") == Some("true"),
+            estimate: config.get("estimate") == Some("true"),
+            bell: config.get("bell") == Some("true"),
+            transcript: config.get("transcript").map(str::to_string),
+            strategy: Strategy::default(),
+            epsilon: 1.0,
+            weights: config.get("weights").map(str::to_string),
+            ponder: config.get("ponder") == Some("true"),
+            think_delay: DEFAULT_THINK_DELAY,
+            p1: None,
+            p2: None,
+            seed: None,
+            position: None,
+            handicaps: Vec::new(),
+            digits: DigitLayout::default(),
+        };
+
+        if let Some(name) = config.get("name") {
+            options.name = Some(parse_name(name)?);
+        }
+        if let Some(name) = config.get("bot_name") {
+            options.bot_name = Some(parse_name(name)?);
+        }
+        if let Some(name) = config.get("theme") {
+            options.theme = parse_theme(name)?;
+        }
+        if let Some(name) = config.get("board") {
+            options.board_style = parse_board_style(name)?;
+        }
+        if let Some(value) = config.get("scale") {
+            options.scale = parse_scale(value)?;
+        }
+        if let Some(name) = config.get("flip") {
+            options.flip = parse_flip(name)?;
+        }
+        if let Some(name) = config.get("numbers") {
+            options.numbers = parse_cell_numbers(name)?;
+        }
+        if let Some(mark) = config.get("side") {
+            options.side = Some(parse_side(mark)?);
+        }
+        if let Some(name) = config.get("difficulty") {
+            options.difficulty = parse_difficulty(name)?;
+        }
+        if let Some(name) = config.get("ai") {
+            options.strategy = parse_strategy(name)?;
+        }
+        if let Some(value) = config.get("epsilon") {
+            options.epsilon = parse_probability(value)?;
+        }
+        if let Some(code) = config.get("lang") {
+            options.language = parse_language(code)?;
+        }
+        if let Some(seconds) = config.get("clock") {
+            options.clock = Some(parse_seconds(seconds)?);
+        }
+        if let Some(seconds) = config.get("move_time") {
+            options.move_time = Some(parse_seconds(seconds)?);
+        }
+        if let Some(value) = config.get("casual_clock") {
+            options.casual_clock = value == "true";
+        }
+        if let Some(seconds) = config.get("idle_timeout") {
+            options.idle_timeout = Some(parse_seconds(seconds)?);
+        }
+        if let Some(value) = config.get("think_delay") {
+            options.think_delay = Duration::from_millis(parse_count(value)?);
+        }
+        if let Some(value) = config.get("seed") {
+            options.seed = Some(parse_count(value)?);
+        }
+        if let Some(value) = config.get("handicap") {
+            options.handicaps = parse_handicaps(value)?;
+        }
+        if let Some(name) = config.get("digits") {
+            options.digits = parse_digit_layout(name)?;
+        }
+
+        let mut p1 = config.get("p1").map(str::to_string);
+        let mut p2 = config.get("p2").map(str::to_string);
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--theme" => options.theme = parse_theme(flag_value(arg, args.next())?)?,
+                "--board" => options.board_style = parse_board_style(flag_value(arg, args.next())?)?,
+                "--flip" => options.flip = parse_flip(flag_value(arg, args.next())?)?,
+                "--numbers" => options.numbers = parse_cell_numbers(flag_value(arg, args.next())?)?,
+                "--scale" => options.scale = parse_scale(flag_value(arg, args.next())?)?,
+                "--lang" => options.language = parse_language(flag_value(arg, args.next())?)?,
+                "--clock" => options.clock = Some(parse_seconds(flag_value(arg, args.next())?)?),
+                "--move-time" => options.move_time = Some(parse_seconds(flag_value(arg, args.next())?)?),
+                "--casual-clock" => options.casual_clock = true,
+                "--name" => options.name = Some(parse_name(flag_value(arg, args.next())?)?),
+                "--bot-name" => options.bot_name = Some(parse_name(flag_value(arg, args.next())?)?),
+                "--difficulty" => options.difficulty = parse_difficulty(flag_value(arg, args.next())?)?,
+                "--ai" => options.strategy = parse_strategy(flag_value(arg, args.next())?)?,
+                "--epsilon" => options.epsilon = parse_probability(flag_value(arg, args.next())?)?,
+                "--weights" => options.weights = Some(flag_value(arg, args.next())?.to_string()),
+                "--ponder" => options.ponder = true,
+                "--think-delay" => options.think_delay = Duration::from_millis(parse_count(flag_value(arg, args.next())?)?),
+                "--tutorial" => options.tutorial = true,
+                "--estimate" => options.estimate = true,
+                "--bell" => options.bell = true,
+                "--transcript" => options.transcript = Some(flag_value(arg, args.next())?.to_string()),
+                "--side" => options.side = Some(parse_side(flag_value(arg, args.next())?)?),
+                "--random-side" => options.random_side = true,
+                "--idle-timeout" => options.idle_timeout = Some(parse_seconds(flag_value(arg, args.next())?)?),
+                "--p1" => p1 = Some(flag_value(arg, args.next())?.to_string()),
+                "--p2" => p2 = Some(flag_value(arg, args.next())?.to_string()),
+                "--position" => options.position = Some(flag_value(arg, args.next())?.to_string()),
+                "--seed" => options.seed = Some(parse_count(flag_value(arg, args.next())?)?),
+                "--handicap" => options.handicaps = parse_handicaps(flag_value(arg, args.next())?)?,
+                "--digits" => options.digits = parse_digit_layout(flag_value(arg, args.next())?)?,
+                _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)]))),
+            }
+        }
+
+        // Built-in difficulties take the global AI settings, so seats are resolved once all flags are in
+        let seat = |name: Option<String>| name.map(|name| agent::parse_seat(&name, config, &options)).transpose();
+        let (p1, p2) = (seat(p1)?, seat(p2)?);
+        options.p1 = p1;
+        options.p2 = p2;
+
+        Ok(options)
+    }
+}
+
+pub fn flag_value<'a>(flag: &str, value: Option<&'a String>) -> Result<&'a str, ParseError> {
+    value
+        .map(|value| value.as_str())
+        .ok_or_else(|| ParseError::new(&tr_args("error.missing_value", &[("flag", &flag)])))
+}
+
+/// A player's name without the spaces around it, as the archive stores it. Blank names are refused.
+fn parse_name(name: &str) -> Result<String, ParseError> {
+    match name.trim() {
+        "" => Err(ParseError::new(tr("error.empty_name"))),
+        name => Ok(name.to_string()),
+    }
+}
+
+fn parse_theme(name: &str) -> Result<Theme, ParseError> {
+    Theme::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Theme::ALL.iter().map(|theme| theme.name()).collect();
+        ParseError::new(&tr_args("error.unknown_theme", &[("name", &name), ("names", &names.join(", "))]))
+    })
+}
+
+fn parse_board_style(name: &str) -> Result<BoardStyle, ParseError> {
+    BoardStyle::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = BoardStyle::ALL.iter().map(|style| style.name()).collect();
+        ParseError::new(&tr_args("error.unknown_board_style", &[("name", &name), ("names", &names.join(", "))]))
+    })
+}
+
+pub fn parse_difficulty(name: &str) -> Result<Difficulty, ParseError> {
+    Difficulty::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Difficulty::ALL.iter().map(|difficulty| difficulty.name()).collect();
+        ParseError::new(&tr_args("error.unknown_difficulty", &[("name", &name), ("names", &names.join(", "))]))
+    })
+}
+
+pub fn parse_strategy(name: &str) -> Result<Strategy, ParseError> {
+    Strategy::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Strategy::ALL.iter().map(|strategy| strategy.name()).collect();
+        ParseError::new(&tr_args("error.unknown_ai", &[("name", &name), ("names", &names.join(", "))]))
+    })
+}
+
+/// A comma-separated list of handicaps, e.g. `no-center, random-first`.
+pub fn parse_handicaps(value: &str) -> Result<Vec<Handicap>, ParseError> {
+    value
+        .split(',')
+        .map(|name| {
+            Handicap::from_name(name).ok_or_else(|| {
+                let names: Vec<&str> = Handicap::ALL.iter().map(|handicap| handicap.name()).collect();
+                ParseError::new(&tr_args("error.unknown_handicap", &[("name", &name.trim()), ("names", &names.join(", "))]))
+            })
+        })
+        .collect()
+}
+
+fn parse_digit_layout(name: &str) -> Result<DigitLayout, ParseError> {
+    DigitLayout::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = DigitLayout::ALL.iter().map(|layout| layout.name()).collect();
+        ParseError::new(&tr_args("error.unknown_digits", &[("name", &name), ("names", &names.join(", "))]))
+    })
+}
+
+fn parse_flip(name: &str) -> Result<Flip, ParseError> {
+    Flip::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Flip::ALL.iter().map(|flip| flip.name()).collect();
+        ParseError::new(&tr_args("error.unknown_flip", &[("name", &name), ("names", &names.join(", "))]))
+    })
+}
+
+fn parse_cell_numbers(name: &str) -> Result<CellNumbers, ParseError> {
+    CellNumbers::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = CellNumbers::ALL.iter().map(|numbers| numbers.name()).collect();
+        ParseError::new(&tr_args("error.unknown_numbers", &[("name", &name), ("names", &names.join(", "))]))
+    })
+}
+
+fn parse_scale(value: &str) -> Result<usize, ParseError> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|scale| (1..=render::MAX_SCALE).contains(scale))
+        .ok_or_else(|| ParseError::new(&tr_args("error.invalid_scale", &[("value", &value), ("max", &render::MAX_SCALE)])))
+}
+
+fn parse_side(mark: &str) -> Result<Player, ParseError> {
+    parse_player(mark).ok_or_else(|| ParseError::new(tr("error.invalid_side")))
+}
+
+fn parse_language(code: &str) -> Result<Language, ParseError> {
+    Language::from_code(code).ok_or_else(|| {
+        let codes: Vec<&str> = Language::ALL.iter().map(|language| language.code()).collect();
+        ParseError::new(&tr_args("error.unknown_language", &[("name", &code), ("names", &codes.join(", "))]))
+    })
+}
+
+pub fn parse_probability(value: &str) -> Result<f64, ParseError> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|probability| (0.0..=1.0).contains(probability))
+        .ok_or_else(|| ParseError::new(&tr_args("error.invalid_probability", &[("value", &value)])))
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, ParseError> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map(Duration::from_secs)
+        .map_err(|_| ParseError::new(&tr_args("error.invalid_seconds", &[("value", &value)])))
+}
+
+pub fn parse_count(value: &str) -> Result<u64, ParseError> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| ParseError::new(&tr_args("error.invalid_count", &[("value", &value)])))
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::{cancellable_best_move, Board, Coordinate, Player};
+
+/// Best replies found so far, keyed by the cells of the position the bot has to answer.
+type Table = HashMap<[Option<Player>; 9], (Coordinate, usize)>;
+
+/// Searches the bot's replies to every move the human might make, on a background thread
+/// while the human is still typing. The results stay in a table shared with the bot's own turn.
+#[derive(Default)]
+pub struct Ponderer {
+    table: Arc<Mutex<Table>>,
+    cancel: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Ponderer {
+    /// Starts pondering the position, which must have the human to move.
+    pub fn start(&mut self, board: &Board, ai_character: Player) {
+        self.stop();
+        self.cancel.store(false, Ordering::Relaxed);
+
+        let board = *board;
+        let table = Arc::clone(&self.table);
+        let cancel = Arc::clone(&self.cancel);
+        self.handle = Some(thread::spawn(move || {
+            for reply in board.empty_cells() {
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let mut position = board;
+                position.highlight = None;
+                position.play_move(reply);
+                position.turn = position.turn.opponent();
+                position.check_if_game_over();
+                if position.winner.is_some() {
+                    continue;
+                }
+
+                let known = table.lock().map(|table| table.contains_key(&position.cells)).unwrap_or(true);
+                if !known {
+                    let Some(result) = cancellable_best_move(&position, ai_character, &cancel) else {
+                        return;
+                    };
+                    if let Ok(mut table) = table.lock() {
+                        table.insert(position.cells, result);
+                    }
+                }
+            }
+        }));
+    }
+
+    /// Cancels the search and waits for the thread, which gives it up at the next position it looks at.
+    pub fn stop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    /// The reply found for this position, if pondering got to it.
+    pub fn lookup(&self, board: &Board) -> Option<(Coordinate, usize)> {
+        self.table.lock().ok().and_then(|table| table.get(&board.cells).copied())
+    }
+}
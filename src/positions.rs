@@ -0,0 +1,172 @@
+//! Everything that can happen in a game. `count-positions` counts the positions that can come up and the
+//! complete games, each both as they are and with rotations and reflections counted once.
+//!
+//! `strategy-table` lists the perfect strategy: every position up to symmetry where the game isn't over,
+//! with the side to move, the result it can force and the moves that force it.
+//!
+//! ```text
+//! strategy-table [--format markdown|csv] [--out <file>]
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::analysis::move_values;
+use crate::error::{GameError, IoError, ParseError};
+use crate::i18n::tr_args;
+use crate::options::flag_value;
+use crate::{cells_key, Board, Coordinate, Player, Winner};
+
+#[derive(Debug, Default)]
+struct Count {
+    /// Distinct positions, the empty board included.
+    positions: HashSet<String>,
+    /// Distinct positions where the game is over.
+    finished: HashSet<String>,
+    /// Complete games won by each player, indexed by player, and drawn.
+    wins: [usize; 2],
+    draws: usize,
+}
+
+impl Count {
+    fn games(&self) -> usize {
+        self.wins.iter().sum::<usize>() + self.draws
+    }
+
+    /// Walks every game from the position. With `symmetric`, moves leading to positions that are rotations
+    /// or reflections of each other are only followed once, and positions are counted by their canonical key.
+    fn walk(&mut self, board: &mut Board, symmetric: bool) {
+        let key = if symmetric { board.canonical_key() } else { cells_key(&board.cells) };
+        self.positions.insert(key.clone());
+        if let Some(winner) = board.winner {
+            self.finished.insert(key);
+            match winner {
+                Winner::Player(player) => self.wins[player as usize] += 1,
+                Winner::Draw => self.draws += 1,
+            }
+            return;
+        }
+
+        let mut followed = HashSet::new();
+        for coord in board.empty_cells() {
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+            board.check_if_game_over();
+            if !symmetric || followed.insert(board.canonical_key()) {
+                self.walk(board, symmetric);
+            }
+            board.undo();
+        }
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    if let Some(arg) = args.first() {
+        return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into());
+    }
+    for symmetric in [false, true] {
+        let mut count = Count::default();
+        count.walk(&mut Board::new(), symmetric);
+        let key = if symmetric { "positions.symmetric" } else { "positions.raw" };
+        println!(
+            "{}",
+            tr_args(
+                key,
+                &[
+                    ("positions", &count.positions.len()),
+                    ("finished", &count.finished.len()),
+                    ("games", &count.games()),
+                    ("o", &Player::O),
+                    ("o_wins", &count.wins[Player::O as usize]),
+                    ("x", &Player::X),
+                    ("x_wins", &count.wins[Player::X as usize]),
+                    ("draws", &count.draws),
+                ],
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Every position where the game isn't over, one per symmetry class, drawn as its canonical key.
+/// Ordered by the number of moves played, then by key.
+fn canonical_positions() -> Vec<Board> {
+    fn walk(board: &mut Board, found: &mut HashMap<String, usize>) {
+        if board.winner.is_some() {
+            return;
+        }
+        let key = board.canonical_key();
+        if found.contains_key(&key) {
+            return;
+        }
+        found.insert(key, board.moves);
+        for coord in board.empty_cells() {
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+            board.check_if_game_over();
+            walk(board, found);
+            board.undo();
+        }
+    }
+
+    let mut found = HashMap::new();
+    walk(&mut Board::new(), &mut found);
+    let mut keys: Vec<(usize, String)> = found.into_iter().map(|(key, moves)| (moves, key)).collect();
+    keys.sort();
+    keys.iter()
+        .filter_map(|(_, key)| Board::from_compact(&format!("{}/{}/{}", &key[0..3], &key[3..6], &key[6..9])).ok())
+        .collect()
+}
+
+pub fn run_strategy_table(args: &[String]) -> Result<(), GameError> {
+    let mut csv = false;
+    let mut out: Option<String> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                csv = match flag_value(arg, args.next())?.trim().to_lowercase().as_str() {
+                    "csv" => true,
+                    "markdown" | "md" => false,
+                    format => return Err(ParseError::new(&tr_args("error.unknown_format", &[("name", &format)])).into()),
+                }
+            }
+            "--out" => out = Some(flag_value(arg, args.next())?.to_string()),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    let mut table = if csv {
+        "position,to_move,result,best_moves\n".to_string()
+    } else {
+        "| Position | To move | Result | Best moves |\n|---|---|---|---|\n".to_string()
+    };
+    for board in canonical_positions() {
+        let values = move_values(&board);
+        let best = values.iter().flatten().max().copied().unwrap_or(0);
+        let result = match best {
+            1 => "win",
+            0 => "draw",
+            _ => "loss",
+        };
+        let moves: Vec<String> =
+            (0..9).filter(|&cell| values[cell] == Some(best)).filter_map(Coordinate::from_index).map(|coord| coord.to_string()).collect();
+        let position = cells_key(&board.cells);
+        let position = format!("{}/{}/{}", &position[0..3], &position[3..6], &position[6..9]);
+        if csv {
+            table.push_str(&format!("{},{},{},{}\n", position, board.turn, result, moves.join(" ")));
+        } else {
+            table.push_str(&format!("| `{}` | {} | {} | {} |\n", position, board.turn, result, moves.join(", ")));
+        }
+    }
+
+    match out {
+        Some(path) => {
+            fs::write(&path, table).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &path)])))?;
+            println!("{}", tr_args("strategy_table.written", &[("path", &path)]));
+        }
+        None => print!("{}", table),
+    }
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Practice from positions worth knowing by heart, the traps and setups that decide most games:
+//!
+//! ```text
+//! practice [--list] [--drill <n>]
+//! ```
+//!
+//! Each drill is played against the engine from its position. Every move is checked against the best result
+//! the position allows, a win to convert or a draw to hold, and one that gives it up ends the attempt with
+//! what went wrong and the moves that would have kept it.
+
+use crate::analysis::{self, format_cells, fork_cells, winning_cells};
+use crate::error::{GameError, ParseError};
+use crate::generate::play;
+use crate::i18n::{tr, tr_args};
+use crate::options::{flag_value, parse_count};
+use crate::{ai_best_move, commands, evaluate, feedback, get_input_from_console, parse_coordinates, Board, Coordinate, Winner};
+
+struct Drill {
+    /// In the `--position` format. The player practising moves first.
+    position: &'static str,
+    /// i18n keys of the name and of what the drill teaches.
+    name: &'static str,
+    about: &'static str,
+}
+
+const DRILLS: &[Drill] = &[
+    Drill { position: "O../.../... X", name: "practice.corner_opening", about: "practice.corner_opening_about" },
+    Drill { position: "O../.X./..O X", name: "practice.opposite_corners", about: "practice.opposite_corners_about" },
+    Drill { position: "X../.O./..O X", name: "practice.center_and_corner", about: "practice.center_and_corner_about" },
+    Drill { position: "OX./.../... O", name: "practice.edge_reply", about: "practice.edge_reply_about" },
+    Drill { position: ".X./.O./... O", name: "practice.center_edge_reply", about: "practice.center_edge_reply_about" },
+    Drill { position: "O.X/.X./..O O", name: "practice.block_and_fork", about: "practice.block_and_fork_about" },
+];
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut first = 1;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--list" => {
+                for (number, drill) in DRILLS.iter().enumerate() {
+                    println!("{:>2}. {}  {}", number + 1, tr(drill.name), drill.position);
+                }
+                return Ok(());
+            }
+            "--drill" => {
+                first = parse_count(flag_value(arg, args.next())?)? as usize;
+                if !(1..=DRILLS.len()).contains(&first) {
+                    return Err(ParseError::new(&tr_args("practice.bad_drill", &[("count", &DRILLS.len())])).into());
+                }
+            }
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    for (number, drill) in DRILLS.iter().enumerate().skip(first - 1) {
+        println!("{}", tr_args("practice.title", &[("number", &(number + 1)), ("name", &tr(drill.name))]));
+        println!("{}", tr(drill.about));
+        while !attempt(drill)? {
+            println!("{}", tr("practice.retry"));
+            if !commands::is_yes(&get_input_from_console()?) {
+                break;
+            }
+        }
+        if number + 1 < DRILLS.len() {
+            println!("{}", tr("practice.next"));
+            if !commands::is_yes(&get_input_from_console()?) {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Plays the drill once against the engine. Returns whether the player kept the best result to the end.
+fn attempt(drill: &Drill) -> Result<bool, GameError> {
+    let mut board = Board::from_compact(drill.position)?;
+    let player = board.turn;
+    let goal = evaluate(&board, player).signum();
+    println!("{}", tr_args(if goal > 0 { "practice.goal_win" } else { "practice.goal_draw" }, &[("player", &player)]));
+
+    while board.winner.is_none() {
+        println!("{}", board);
+        if board.turn != player {
+            let engine = board.turn;
+            let (reply, _) = ai_best_move(&board, engine);
+            println!("{}", tr_args("explore.engine", &[("move", &reply)]));
+            play(&mut board, reply);
+            continue;
+        }
+
+        println!("{}", tr_args("prompt.your_turn", &[("player", &player.label())]));
+        let input = get_input_from_console()?;
+        let coord = match parse_coordinates(input.clone()) {
+            Ok(coord) if board.get_cell(coord).is_none() => coord,
+            Ok(_) => {
+                println!("{}", tr_args("error.prefix", &[("message", &tr("error.cell_taken"))]));
+                continue;
+            }
+            Err(e) => {
+                feedback::show(&board, &input, &e.into());
+                continue;
+            }
+        };
+
+        let values = analysis::move_values(&board);
+        if values[coord.index()].is_some_and(|value| value < goal) {
+            explain_failure(&board, coord, goal);
+            println!("{}", analysis::hint(&values));
+            return Ok(false);
+        }
+        play(&mut board, coord);
+    }
+
+    println!("{}", board);
+    let key = match board.winner {
+        Some(Winner::Player(winner)) if winner == player => "practice.won",
+        _ => "practice.held",
+    };
+    println!("{}", tr(key));
+    Ok(true)
+}
+
+/// Says why the move gives up the result: a win that was there to take, a threat left open, or a fork
+/// the opponent gets. Otherwise just that the result is gone.
+fn explain_failure(before: &Board, coord: Coordinate, goal: i32) {
+    let (player, opponent) = (before.turn, before.turn.opponent());
+    let mut after = *before;
+    play(&mut after, coord);
+
+    let wins = winning_cells(before, player);
+    let threats = winning_cells(before, opponent);
+    let forks = fork_cells(&after, opponent);
+    let reason = if !wins.is_empty() {
+        tr_args("practice.missed_win", &[("cells", &format_cells(&wins))])
+    } else if !threats.is_empty() && !threats.contains(&coord.index()) {
+        tr_args("practice.missed_block", &[("cells", &format_cells(&threats))])
+    } else if !forks.is_empty() && winning_cells(&after, player).is_empty() {
+        tr_args("practice.allows_fork", &[("cells", &format_cells(&forks))])
+    } else if goal > 0 {
+        tr("practice.lets_win_go").to_string()
+    } else {
+        tr("practice.loses").to_string()
+    };
+    println!("{}", tr_args("practice.failed", &[("move", &coord), ("reason", &reason)]));
+}
@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::archive::parse_player;
+use crate::config::{data_dir, safe_file_name, Config};
+use crate::coordinates::DigitLayout;
+use crate::difficulty::Difficulty;
+use crate::error::{GameError, IoError};
+use crate::i18n::tr_args;
+use crate::options::Options;
+use crate::render::Flip;
+use crate::save_format;
+use crate::theme::Theme;
+use crate::Player;
+
+/// Blunder rate a new player starts with in adaptive mode.
+const STARTING_BLUNDER_RATE: f64 = 0.5;
+const MAX_BLUNDER_RATE: f64 = 0.9;
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+/// A human player's record against the bot, kept between runs under the data directory.
+#[derive(Debug)]
+pub struct Profile {
+    pub name: String,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    /// The adaptive bot's current blunder rate against this player.
+    pub blunder_rate: f64,
+    /// Games won in a row if positive, lost in a row if negative. A draw ends either.
+    pub streak: i32,
+    /// The settings of the player's last game, used for the next one unless given otherwise.
+    pub side: Option<Player>,
+    pub difficulty: Option<Difficulty>,
+    pub theme: Option<Theme>,
+    pub digits: Option<DigitLayout>,
+    pub scale: Option<usize>,
+    pub flip: Option<Flip>,
+}
+
+impl Profile {
+    /// Loads the named profile, starting a fresh one if it doesn't exist yet.
+    pub fn load(name: &str) -> Result<Profile, GameError> {
+        let mut profile = Profile {
+            name: name.to_string(),
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            blunder_rate: STARTING_BLUNDER_RATE,
+            streak: 0,
+            side: None,
+            difficulty: None,
+            theme: None,
+            digits: None,
+            scale: None,
+            flip: None,
+        };
+
+        let Ok(text) = fs::read_to_string(profile.path()) else {
+            return Ok(profile);
+        };
+        let config = Config::parse(&save_format::upgrade(&text, &profile.path())?)?;
+        let number = |key: &str| config.get(key).and_then(|value| value.parse::<u32>().ok()).unwrap_or(0);
+        profile.wins = number("wins");
+        profile.draws = number("draws");
+        profile.losses = number("losses");
+        if let Some(rate) = config.get("blunder_rate").and_then(|value| value.parse::<f64>().ok()) {
+            profile.blunder_rate = rate.clamp(0.0, MAX_BLUNDER_RATE);
+        }
+        profile.streak = config.get("streak").and_then(|value| value.parse().ok()).unwrap_or(0);
+        profile.side = config.get("side").and_then(parse_player);
+        profile.difficulty = config.get("difficulty").and_then(Difficulty::from_name);
+        profile.theme = config.get("theme").and_then(Theme::from_name);
+        profile.digits = config.get("digits").and_then(DigitLayout::from_name);
+        profile.scale = config.get("scale").and_then(|value| value.parse().ok());
+        profile.flip = config.get("flip").and_then(Flip::from_name);
+
+        Ok(profile)
+    }
+
+    pub fn save(&self) -> Result<(), IoError> {
+        let path = self.path();
+        let mut text = format!(
+            "{}name = {}\nwins = {}\ndraws = {}\nlosses = {}\nblunder_rate = {:.2}\nstreak = {}\n",
+            save_format::header(),
+            self.name, self.wins, self.draws, self.losses, self.blunder_rate, self.streak
+        );
+        for (key, value) in self.preferences() {
+            text.push_str(&format!("{} = {}\n", key, value));
+        }
+        path.parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| fs::write(&path, text))
+            .map_err(|_| IoError::new(&tr_args("error.save_profile", &[("name", &self.name)])))
+    }
+
+    /// Counts a finished game and moves the adaptive blunder rate: losing makes the bot
+    /// weaker, winning makes it stronger, and drawing tightens it a little.
+    pub fn record(&mut self, outcome: Outcome) {
+        let step = match outcome {
+            Outcome::Win => {
+                self.wins += 1;
+                -0.1
+            }
+            Outcome::Draw => {
+                self.draws += 1;
+                -0.05
+            }
+            Outcome::Loss => {
+                self.losses += 1;
+                0.1
+            }
+        };
+        self.blunder_rate = (self.blunder_rate + step).clamp(0.0, MAX_BLUNDER_RATE);
+        self.streak = match outcome {
+            Outcome::Win => self.streak.max(0) + 1,
+            Outcome::Draw => 0,
+            Outcome::Loss => self.streak.min(0) - 1,
+        };
+    }
+
+    /// Keeps the settings of the game just played for the next one. The side only counts if the
+    /// player picked it, and the difficulty only if the usual bot was played.
+    pub fn remember(&mut self, options: &Options, side: Player) {
+        let usual_game = options.p1.is_none() && options.p2.is_none();
+        if usual_game && !options.random_side {
+            self.side = Some(side);
+        }
+        if usual_game {
+            self.difficulty = Some(options.difficulty);
+        }
+        self.theme = Some(options.theme);
+        self.digits = Some(options.digits);
+        self.scale = Some(options.scale);
+        self.flip = Some(options.flip);
+    }
+
+    /// The remembered settings as config keys and values.
+    fn preferences(&self) -> Vec<(&'static str, String)> {
+        [
+            ("side", self.side.map(|side| side.to_string())),
+            ("difficulty", self.difficulty.map(|difficulty| difficulty.name().to_string())),
+            ("theme", self.theme.map(|theme| theme.name().to_string())),
+            ("digits", self.digits.map(|digits| digits.name().to_string())),
+            ("scale", self.scale.map(|scale| scale.to_string())),
+            ("flip", self.flip.map(|flip| flip.name().to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect()
+    }
+
+    /// Puts the remembered settings into the config, over what the config file says.
+    pub fn apply(&self, config: &mut Config) {
+        for (key, value) in self.preferences() {
+            config.set(key, &value);
+        }
+    }
+
+    /// Welcomes a returning player with their record and streak. Nothing for a new one.
+    pub fn greeting(&self) -> Option<String> {
+        if self.wins + self.draws + self.losses == 0 {
+            return None;
+        }
+        let mut text = tr_args(
+            "profile.welcome_back",
+            &[("name", &self.name), ("wins", &self.wins), ("draws", &self.draws), ("losses", &self.losses)],
+        );
+        match self.streak {
+            2.. => text.push_str(&format!("\n{}", tr_args("profile.win_streak", &[("count", &self.streak)]))),
+            ..=-2 => text.push_str(&format!("\n{}", tr_args("profile.loss_streak", &[("count", &-self.streak)]))),
+            _ => {}
+        }
+        Some(text)
+    }
+
+    fn path(&self) -> PathBuf {
+        data_dir().join("profiles").join(safe_file_name(&self.name))
+    }
+}
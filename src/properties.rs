@@ -0,0 +1,137 @@
+//! Rules that should hold for every position, checked on random positions. A position breaking one is named
+//! in compact form in the failure, and the fixed seed finds it again.
+
+use crate::generate::random_position;
+use crate::{ai_best_move, cells_key, evaluate, expectimax, seeded_rng, Board, Winner};
+
+/// Positions each property is checked on.
+const CASES: usize = 200;
+const SEED: u64 = 0;
+
+/// Runs the check on random positions, failing on the first that breaks it.
+fn check_all(check: fn(&Board) -> Result<(), String>) {
+    let mut rng = seeded_rng(Some(SEED));
+    for _ in 0..CASES {
+        let board = random_position(&mut rng);
+        if let Err(problem) = check(&board) {
+            panic!("{} fails: {}", board.to_compact_string(), problem);
+        }
+    }
+}
+
+#[test]
+fn symmetry() {
+    check_all(check_symmetry);
+}
+
+#[test]
+fn solver() {
+    check_all(check_solver);
+}
+
+#[test]
+fn undo() {
+    check_all(check_undo);
+}
+
+#[test]
+fn search_after_undo() {
+    check_all(check_search_after_undo);
+}
+
+#[test]
+fn compact() {
+    check_all(check_compact);
+}
+
+#[test]
+fn game_over() {
+    check_all(check_game_over);
+}
+
+/// The other positions the rotations and reflections of the board turn it into, each once.
+fn transformed(board: &Board) -> Vec<Board> {
+    let mut states = board.generate_all_equivalent_states();
+    states.sort_by_key(cells_key);
+    states.dedup();
+    states
+        .into_iter()
+        .filter(|cells| *cells != board.cells)
+        .map(|cells| {
+            let mut other = Board { cells, turn: board.turn, ..Board::new() };
+            other.check_if_game_over();
+            other
+        })
+        .collect()
+}
+
+/// Every rotation and reflection has the same canonical key.
+fn check_symmetry(board: &Board) -> Result<(), String> {
+    let key = board.canonical_key();
+    match transformed(board).iter().find(|other| other.canonical_key() != key) {
+        Some(other) => Err(format!("{} has key {}", other.to_compact_string(), other.canonical_key())),
+        None => Ok(()),
+    }
+}
+
+/// Every rotation and reflection has the same minimax result.
+fn check_solver(board: &Board) -> Result<(), String> {
+    let value = evaluate(board, board.turn).signum();
+    match transformed(board).iter().find(|other| evaluate(other, other.turn).signum() != value) {
+        Some(other) => Err(format!("{} scores differently", other.to_compact_string())),
+        None => Ok(()),
+    }
+}
+
+/// Taking back every move leads to the empty board, and redoing them all back to the position.
+fn check_undo(board: &Board) -> Result<(), String> {
+    let mut other = *board;
+    while other.undo().is_some() {}
+    if other.cells != Board::new().cells || other.turn != Board::new().turn || other.winner.is_some() {
+        return Err(format!("undoing every move leaves {}", other.to_compact_string()));
+    }
+    while other.redo().is_some() {}
+    if other.cells != board.cells || other.turn != board.turn || other.winner != board.winner {
+        return Err(format!("redoing every move leads to {}", other.to_compact_string()));
+    }
+    Ok(())
+}
+
+/// Searching for a move after taking one back leaves the board as it was, so the move can still be redone.
+fn check_search_after_undo(board: &Board) -> Result<(), String> {
+    let mut other = *board;
+    if other.undo().is_none() || other.winner.is_some() {
+        return Ok(());
+    }
+    let before = other;
+    ai_best_move(&other, other.turn);
+    expectimax::best_move(&other, other.turn, 0.5);
+    if other != before {
+        return Err(format!("searching from {} changes the board", before.to_compact_string()));
+    }
+    match other.redo() {
+        Some(_) if other.cells == board.cells => Ok(()),
+        _ => Err(format!("searching from {} loses the redo", before.to_compact_string())),
+    }
+}
+
+/// The compact form reads back as the same cells, turn and result.
+fn check_compact(board: &Board) -> Result<(), String> {
+    let compact = board.to_compact_string();
+    match Board::from_compact(&compact) {
+        Ok(other) if other.cells == board.cells && other.turn == board.turn && other.winner == board.winner => Ok(()),
+        Ok(other) => Err(format!("reads back as {}", other.to_compact_string())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The game is over exactly when a line is made or the board is full, and a line is won by its mark.
+fn check_game_over(board: &Board) -> Result<(), String> {
+    let line_owner = board.winning_line().and_then(|line| board.cells[line[0]]);
+    let consistent = match board.winner {
+        Some(Winner::Player(player)) => line_owner == Some(player),
+        Some(Winner::Draw) => line_owner.is_none() && board.is_full(),
+        None => line_owner.is_none() && !board.is_full(),
+    };
+    if consistent { Ok(()) } else { Err(format!("the result is {:?}", board.winner)) }
+}
@@ -0,0 +1,217 @@
+//! Finds the mistakes in played games and turns them into puzzles:
+//!
+//! ```text
+//! puzzles [--file <selfplay file>] [--out <file>]
+//! ```
+//!
+//! A mistake is a move that let a forced win slip, or that handed the opponent one. The puzzle is the
+//! position before it, to be solved by finding the moves that keep the best result. With `--out` the
+//! puzzles are written in the format of the config file:
+//!
+//! ```text
+//! [puzzle.1]
+//! position = O../.X./..O X
+//! solution = 1-3 3-1
+//! kind = allowed_win
+//! game = 3
+//! move = 4
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::archive;
+use crate::error::{GameError, IoError, ParseError};
+use crate::i18n::{tr, tr_args};
+use crate::options::flag_value;
+use crate::replay::read_games;
+use crate::{evaluate, Board, Coordinate, Player, Winner};
+
+/// What went wrong in the game at the puzzle's position.
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+pub enum Mistake {
+    /// The player could force a win and played a move that can't.
+    MissedWin,
+    /// The player could hold at least a draw and played a move that loses.
+    AllowedWin,
+}
+
+impl Mistake {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mistake::MissedWin => "missed_win",
+            Mistake::AllowedWin => "allowed_win",
+        }
+    }
+}
+
+pub struct Puzzle {
+    pub position: Board,
+    /// The moves keeping the best result, any of them solves the puzzle.
+    pub solution: Vec<Coordinate>,
+    pub mistake: Mistake,
+    /// The game it came from, and the number of the move that went wrong.
+    pub game: u64,
+    pub move_number: usize,
+    /// Name of the player who went wrong.
+    pub player: String,
+}
+
+/// A game to look for mistakes in.
+struct Game {
+    number: u64,
+    start: Board,
+    moves: Vec<Coordinate>,
+    /// Names of the players, indexed by player.
+    players: [String; 2],
+}
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut file: Option<String> = None;
+    let mut out: Option<String> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => file = Some(flag_value(arg, args.next())?.to_string()),
+            "--out" => out = Some(flag_value(arg, args.next())?.to_string()),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    let games = match &file {
+        Some(path) => selfplay_games(path)?,
+        None => archive_games()?,
+    };
+    let puzzles = find_puzzles(&games);
+
+    for puzzle in &puzzles {
+        let kind = match puzzle.mistake {
+            Mistake::MissedWin => tr("puzzles.missed_win"),
+            Mistake::AllowedWin => tr("puzzles.allowed_win"),
+        };
+        println!(
+            "{}",
+            tr_args(
+                "puzzles.found",
+                &[
+                    ("game", &puzzle.game),
+                    ("move", &puzzle.move_number),
+                    ("player", &puzzle.player),
+                    ("kind", &kind),
+                    ("position", &puzzle.position.to_compact_string()),
+                    ("solution", &moves_text(&puzzle.solution)),
+                ],
+            )
+        );
+    }
+    println!("{}", tr_args("puzzles.summary", &[("puzzles", &puzzles.len()), ("games", &games.len())]));
+
+    if let Some(path) = out {
+        fs::write(&path, to_config(&puzzles)).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &path)])))?;
+        println!("{}", tr_args("puzzles.written", &[("path", &path)]));
+    }
+    Ok(())
+}
+
+fn archive_games() -> Result<Vec<Game>, GameError> {
+    archive::load()?
+        .into_iter()
+        .map(|game| Ok(Game { number: game.id, start: game.start_board()?, moves: game.moves, players: game.players }))
+        .collect()
+}
+
+fn selfplay_games(path: &str) -> Result<Vec<Game>, GameError> {
+    Ok(read_games(path)?
+        .into_iter()
+        .map(|game| Game {
+            number: game.number,
+            start: Board::new(),
+            moves: game.moves.iter().filter_map(|&cell| Coordinate::from_index(cell)).collect(),
+            players: [Player::X.to_string(), Player::O.to_string()],
+        })
+        .collect())
+}
+
+/// Every mistake in the games, each position only once however many games reached it.
+fn find_puzzles(games: &[Game]) -> Vec<Puzzle> {
+    let mut values = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut puzzles = Vec::new();
+
+    for game in games {
+        let mut board = game.start;
+        for (number, &coord) in game.moves.iter().enumerate() {
+            if board.winner.is_some() || board.get_cell(coord).is_some() {
+                break;
+            }
+            let before = board;
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+            board.check_if_game_over();
+
+            let best = value(&before, &mut values);
+            let played = -value(&board, &mut values);
+            let mistake = match (best, played) {
+                (1, played) if played < 1 => Mistake::MissedWin,
+                (0, -1) => Mistake::AllowedWin,
+                _ => continue,
+            };
+            if !seen.insert(before.canonical_key()) {
+                continue;
+            }
+            let solution = before
+                .empty_cells()
+                .into_iter()
+                .filter(|&cell| {
+                    let mut after = before;
+                    after.play_move(cell);
+                    after.turn = after.turn.opponent();
+                    after.check_if_game_over();
+                    -value(&after, &mut values) == best
+                })
+                .collect();
+            puzzles.push(Puzzle {
+                position: before,
+                solution,
+                mistake,
+                game: game.number,
+                move_number: number + 1,
+                player: game.players[before.turn as usize].clone(),
+            });
+        }
+    }
+    puzzles
+}
+
+/// The result the side to move can force: 1 win, 0 draw, -1 loss. Symmetric positions share an entry.
+fn value(board: &Board, values: &mut HashMap<String, i32>) -> i32 {
+    if let Some(winner) = board.winner {
+        // The player who just moved made the line, if there is one
+        return if winner == Winner::Draw { 0 } else { -1 };
+    }
+    *values.entry(board.canonical_key()).or_insert_with(|| evaluate(board, board.turn).signum())
+}
+
+fn moves_text(moves: &[Coordinate]) -> String {
+    moves.iter().map(Coordinate::to_string).collect::<Vec<String>>().join(" ")
+}
+
+fn to_config(puzzles: &[Puzzle]) -> String {
+    puzzles
+        .iter()
+        .enumerate()
+        .map(|(number, puzzle)| {
+            format!(
+                "[puzzle.{}]\nposition = {}\nsolution = {}\nkind = {}\ngame = {}\nmove = {}\n",
+                number + 1,
+                puzzle.position.to_compact_string(),
+                moves_text(&puzzle.solution),
+                puzzle.mistake.name(),
+                puzzle.game,
+                puzzle.move_number
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
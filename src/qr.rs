@@ -0,0 +1,213 @@
+//! QR codes for short texts, drawn in the terminal so that a share code can be scanned off the screen.
+//! Only what share codes need: byte mode, the lowest error correction level and versions 1 to 3, up to
+//! 53 bytes, always with the first mask pattern.
+
+/// Total and error correction codewords of versions 1 to 3 at level L, all in one block.
+const VERSIONS: [(usize, usize); 3] = [(26, 7), (44, 10), (70, 15)];
+
+/// Modules left light around the code, the margin scanners expect.
+const QUIET_ZONE: usize = 4;
+
+/// The dark modules of the code, row by row. None if the text is too long for version 3.
+pub fn encode(text: &str) -> Option<Vec<Vec<bool>>> {
+    let bytes = text.as_bytes();
+    let version = VERSIONS.iter().position(|&(total, ec)| bytes.len() + 2 <= total - ec)? + 1;
+    let (total, ec) = VERSIONS[version - 1];
+
+    // Mode, length, the bytes, a terminator, then padding bytes up to the capacity
+    let mut bits = Bits::default();
+    bits.push(0b0100, 4);
+    bits.push(bytes.len() as u32, 8);
+    for &byte in bytes {
+        bits.push(byte as u32, 8);
+    }
+    let capacity = (total - ec) * 8;
+    bits.push(0, (capacity - bits.0.len()).min(4));
+    bits.push(0, (8 - bits.0.len() % 8) % 8);
+    for pad in [0xEC, 0x11].iter().cycle().take(capacity / 8 - bits.0.len() / 8) {
+        bits.push(*pad, 8);
+    }
+    let mut codewords: Vec<u8> = bits.0.chunks(8).map(|byte| byte.iter().fold(0, |value, &bit| value << 1 | bit as u8)).collect();
+    codewords.extend(error_correction(&codewords, ec));
+
+    let mut code = Code::new(version * 4 + 17);
+    code.draw_function_patterns(version);
+    code.draw_codewords(&codewords);
+    Some(code.modules)
+}
+
+/// The code in half blocks, two rows of modules to a line, light modules drawn so that it reads on a dark
+/// terminal.
+pub fn render(modules: &[Vec<bool>]) -> String {
+    let size = modules.len() + QUIET_ZONE * 2;
+    let dark = |row: usize, col: usize| {
+        let (row, col) = (row.wrapping_sub(QUIET_ZONE), col.wrapping_sub(QUIET_ZONE));
+        modules.get(row).and_then(|cells| cells.get(col)).copied().unwrap_or(false)
+    };
+    let mut text = String::new();
+    for row in (0..size).step_by(2) {
+        for col in 0..size {
+            let bottom_dark = row + 1 >= size || dark(row + 1, col);
+            text.push(match (dark(row, col), bottom_dark) {
+                (false, false) => '█',
+                (false, true) => '▀',
+                (true, false) => '▄',
+                (true, true) => ' ',
+            });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[derive(Default)]
+struct Bits(Vec<bool>);
+
+impl Bits {
+    /// Appends the lowest `count` bits of the value, highest first.
+    fn push(&mut self, value: u32, count: usize) {
+        self.0.extend((0..count).rev().map(|bit| value >> bit & 1 == 1));
+    }
+}
+
+struct Code {
+    modules: Vec<Vec<bool>>,
+    /// Modules of the finder, timing and alignment patterns and of the format, which data can't go in.
+    function: Vec<Vec<bool>>,
+}
+
+impl Code {
+    fn new(size: usize) -> Code {
+        Code { modules: vec![vec![false; size]; size], function: vec![vec![false; size]; size] }
+    }
+
+    fn size(&self) -> usize {
+        self.modules.len()
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y][x] = dark;
+        self.function[y][x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size();
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        for (x, y) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            self.draw_square(x, y, 4, |distance| distance != 2 && distance != 4);
+        }
+        if version > 1 {
+            self.draw_square(size - 7, size - 7, 2, |distance| distance != 1);
+        }
+        self.draw_format();
+    }
+
+    /// Draws the modules up to `reach` away from the center, dark or light by their distance from it.
+    fn draw_square(&mut self, x: usize, y: usize, reach: isize, dark: fn(isize) -> bool) {
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let (column, row) = (x.checked_add_signed(dx), y.checked_add_signed(dy));
+                if let (Some(column), Some(row)) = (column.filter(|&c| c < self.size()), row.filter(|&r| r < self.size())) {
+                    self.set_function(column, row, dark(dx.abs().max(dy.abs())));
+                }
+            }
+        }
+    }
+
+    /// The level and mask in both copies of the format information, with its error correction bits.
+    fn draw_format(&mut self) {
+        // Level L is 01 and the mask pattern 000
+        let data: u32 = 0b01 << 3;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: usize| bits >> i & 1 == 1;
+
+        let size = self.size();
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    /// Fills the data modules two columns at a time from the bottom right, up and down in turn, skipping the
+    /// vertical timing pattern, and applies the mask where `x + y` is even.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size();
+        let mut i = 0;
+        let mut right = size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vertical } else { vertical };
+                    if self.function[y][x] {
+                        continue;
+                    }
+                    let dark = i < codewords.len() * 8 && codewords[i / 8] >> (7 - i % 8) & 1 == 1;
+                    self.modules[y][x] = dark != (x + y).is_multiple_of(2);
+                    i += 1;
+                }
+            }
+            right = right.saturating_sub(2);
+        }
+    }
+}
+
+/// The Reed-Solomon error correction codewords for the data.
+fn error_correction(data: &[u8], degree: usize) -> Vec<u8> {
+    // The generator polynomial, the product of (x - 2^i) for i below the degree, highest term left out
+    let mut divisor = vec![0; degree];
+    divisor[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            divisor[j] = multiply(divisor[j], root);
+            if j + 1 < degree {
+                divisor[j] ^= divisor[j + 1];
+            }
+        }
+        root = multiply(root, 2);
+    }
+
+    let mut remainder = vec![0; degree];
+    for &byte in data {
+        let factor = byte ^ remainder.remove(0);
+        remainder.push(0);
+        for (value, &coefficient) in remainder.iter_mut().zip(&divisor) {
+            *value ^= multiply(coefficient, factor);
+        }
+    }
+    remainder
+}
+
+/// Multiplication in GF(2^8) modulo the QR code polynomial x^8 + x^4 + x^3 + x^2 + 1.
+fn multiply(x: u8, y: u8) -> u8 {
+    let mut product: u32 = 0;
+    for i in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11D);
+        product ^= (y as u32 >> i & 1) * x as u32;
+    }
+    product as u8
+}
@@ -0,0 +1,413 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::analysis::{format_cells, winning_cells};
+use crate::coordinates;
+use crate::i18n::{tr, tr_args};
+use crate::{cells_key, Board, Coordinate, Player, Winner};
+
+/// Draws a board as text.
+pub trait Renderer {
+    fn render(&self, board: &Board) -> String;
+}
+
+/// The default: a Unicode grid with row and column numbers, in the board's theme.
+pub struct Pretty;
+
+/// The same grid drawn with plain ASCII, for terminals without box-drawing characters.
+pub struct Ascii;
+
+/// One line, rows separated by slashes and followed by the side to move, e.g. `O.X/.O./... X`.
+pub struct Compact;
+
+/// A JSON object for other programs, with the cells in the same format as the self-play data.
+pub struct Json;
+
+/// Plain sentences, one per row, for screen readers.
+pub struct Accessible;
+
+impl Renderer for Pretty {
+    fn render(&self, board: &Board) -> String {
+        pretty_grid(board, |index| cell(board, index))
+    }
+}
+
+impl Renderer for Ascii {
+    fn render(&self, board: &Board) -> String {
+        ascii_grid(board, |index| cell(board, index))
+    }
+}
+
+/// The characters a grid is drawn with. Each border is its left corner, the line along a cell, the joint
+/// between cells and the right corner.
+struct GridStyle {
+    top: [&'static str; 4],
+    middle: [&'static str; 4],
+    bottom: [&'static str; 4],
+    outer: &'static str,
+    inner: &'static str,
+    /// Columns left of the grid, where the row numbers go.
+    indent: usize,
+}
+
+const PRETTY_GRID: GridStyle = GridStyle {
+    top: ["╔", "═", "╤", "╗"],
+    middle: ["╟", "─", "┼", "╢"],
+    bottom: ["╚", "═", "╧", "╝"],
+    outer: "║",
+    inner: "│",
+    indent: 7,
+};
+
+const ASCII_GRID: GridStyle = GridStyle {
+    top: ["+", "-", "+", "+"],
+    middle: ["+", "-", "+", "+"],
+    bottom: ["+", "-", "+", "+"],
+    outer: "|",
+    inner: "|",
+    indent: 2,
+};
+
+/// The largest `--scale`.
+pub const MAX_SCALE: usize = 3;
+
+/// Columns and lines a cell takes up at least at each scale, enough for the big marks of `Theme::big_mark`.
+const CELL_SIZES: [(usize, usize); MAX_SCALE] = [(3, 1), (7, 3), (11, 5)];
+
+fn pretty_grid(board: &Board, c: impl Fn(usize) -> String) -> String {
+    grid(&PRETTY_GRID, board, c)
+}
+
+fn ascii_grid(board: &Board, c: impl Fn(usize) -> String) -> String {
+    grid(&ASCII_GRID, board, c)
+}
+
+/// Lays out the cells with row and column numbers, in the board's flip. Every column is as wide as its
+/// widest cell and every row as tall as its tallest one, cells of several lines included, at least the cell
+/// size of the board's scale, and cells are centered in their space, so that any symbols line up.
+fn grid(style: &GridStyle, board: &Board, c: impl Fn(usize) -> String) -> String {
+    let (width, height) = CELL_SIZES[board.scale.clamp(1, MAX_SCALE) - 1];
+    // The rows and columns of the board in the order they are drawn
+    let order = |reversed: bool| if reversed { [2, 1, 0] } else { [0, 1, 2] };
+    let (rows, cols) = (order(board.flip.reverses_rows()), order(board.flip.reverses_columns()));
+    let cells: Vec<Vec<String>> = (0..9).map(|index| c(rows[index / 3] * 3 + cols[index % 3]).lines().map(str::to_string).collect()).collect();
+    let widths: Vec<usize> = (0..3)
+        .map(|col| (0..3).flat_map(|row| &cells[row * 3 + col]).map(|line| display_width(line)).fold(width, usize::max))
+        .collect();
+    let heights: Vec<usize> = (0..3).map(|row| cells[row * 3..row * 3 + 3].iter().map(Vec::len).fold(height, usize::max)).collect();
+
+    let header: Vec<String> = widths.iter().enumerate().map(|(col, &width)| center(&(cols[col] + 1).to_string(), width)).collect();
+    let mut text = format!("{}{}", " ".repeat(style.indent + 1), header.join(" ")).trim_end().to_string();
+    text.push('\n');
+    let border = |[left, line, joint, right]: [&str; 4]| {
+        let lines: Vec<String> = widths.iter().map(|&width| line.repeat(width)).collect();
+        format!("{}{}{}{}\n", " ".repeat(style.indent), left, lines.join(joint), right)
+    };
+    text.push_str(&border(style.top));
+    for row in 0..3 {
+        if row > 0 {
+            text.push_str(&border(style.middle));
+        }
+        for line in 0..heights[row] {
+            let label = if line == heights[row] / 2 { (rows[row] + 1).to_string() } else { String::new() };
+            let row_cells: Vec<String> = (0..3)
+                .map(|col| {
+                    let cell = &cells[row * 3 + col];
+                    let top = (heights[row] - cell.len()) / 2;
+                    let text = line.checked_sub(top).and_then(|line| cell.get(line)).map_or("", String::as_str);
+                    center(text, widths[col])
+                })
+                .collect();
+            text.push_str(&format!("{:>width$} {}{}{}\n", label, style.outer, row_cells.join(style.inner), style.outer, width = style.indent - 1));
+        }
+    }
+    text.push_str(&border(style.bottom));
+    text
+}
+
+/// The text with spaces on both sides to fill the width, one more on the right if it doesn't split evenly.
+fn center(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}{}", " ".repeat(padding / 2), text, " ".repeat(padding - padding / 2))
+}
+
+/// The columns the text takes up in a terminal: color codes take none, combining marks and variation
+/// selectors none, East Asian wide characters and emoji two.
+fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            // Skip the escape sequence up to its final letter
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            continue;
+        }
+        width += match c as u32 {
+            0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+            0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 => 2,
+            // The symbols and dingbats drawn as emoji, like ❌ and ⭕, among the narrow ones around them
+            0x231A..=0x231B | 0x23E9..=0x23EC | 0x23F0 | 0x23F3 | 0x25FD..=0x25FE | 0x2614..=0x2615 | 0x2648..=0x2653 | 0x267F | 0x2693 => 2,
+            0x26A1 | 0x26AA..=0x26AB | 0x26BD..=0x26BE | 0x26C4..=0x26C5 | 0x26CE | 0x26D4 | 0x26EA | 0x26F2..=0x26F3 | 0x26F5 | 0x26FA | 0x26FD => 2,
+            0x2705 | 0x270A..=0x270B | 0x2728 | 0x274C | 0x274E | 0x2753..=0x2755 | 0x2757 | 0x2795..=0x2797 | 0x27B0 | 0x27BF => 2,
+            0x2B1B..=0x2B1C | 0x2B50 | 0x2B55 => 2,
+            0x1F300..=0x1F64F | 0x1F680..=0x1F6FF | 0x1F900..=0x1F9FF | 0x1FA70..=0x1FAFF | 0x20000..=0x3FFFD => 2,
+            _ => 1,
+        };
+    }
+    width
+}
+
+impl Renderer for Compact {
+    fn render(&self, board: &Board) -> String {
+        format!("{}\n", board.to_compact_string())
+    }
+}
+
+impl Renderer for Json {
+    fn render(&self, board: &Board) -> String {
+        let quoted = |text: String| format!("\"{}\"", text);
+        let winner = match board.winner {
+            Some(Winner::Player(player)) => quoted(player.to_string()),
+            Some(Winner::Draw) => quoted("draw".to_string()),
+            None => "null".to_string(),
+        };
+        let highlight = board.highlight.map_or("null".to_string(), |coord| quoted(coord.to_string()));
+        format!(
+            "{{\"position\": \"{}\", \"to_move\": \"{}\", \"winner\": {}, \"highlight\": {}}}\n",
+            cells_key(&board.cells),
+            board.turn,
+            winner,
+            highlight
+        )
+    }
+}
+
+impl Renderer for Accessible {
+    fn render(&self, board: &Board) -> String {
+        let mut text = String::new();
+        for (row, line) in Board::rows().enumerate() {
+            let cells: Vec<String> = board
+                .line_cells(line)
+                .iter()
+                .map(|cell| cell.map_or(tr("render.empty").to_string(), |player| player.to_string()))
+                .collect();
+            text.push_str(&tr_args("render.row", &[("row", &(row + 1)), ("cells", &cells.join(", "))]));
+            text.push('\n');
+        }
+        if let Some(line) = board.winning_line() {
+            text.push_str(&tr_args("render.winning_line", &[("cells", &format_cells(&line))]));
+            text.push('\n');
+        }
+        if let Some(coord) = board.highlight {
+            text.push_str(&tr_args("render.selected", &[("move", &coord)]));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// The board with every empty cell marked with the result of playing there, `+` a win, `=` a draw and
+/// `-` a loss, in the board's theme. The styles without a grid list the cells instead.
+pub fn heat_map(board: &Board, values: &[Option<i32>; 9]) -> String {
+    let c = |index: usize| match values[index] {
+        Some(value) if board.cells[index].is_none() => board.theme.evaluation(value),
+        _ => cell(board, index),
+    };
+    match board.style {
+        BoardStyle::Pretty => format!("{}{}\n", pretty_grid(board, c), tr("hint.legend")),
+        BoardStyle::Ascii => format!("{}{}\n", ascii_grid(board, c), tr("hint.legend")),
+        BoardStyle::Compact | BoardStyle::Json | BoardStyle::Text => {
+            let mut text = String::new();
+            for (key, value) in [("hint.wins_at", 1), ("hint.draws_at", 0), ("hint.loses_at", -1)] {
+                let cells: Vec<usize> = (0..9).filter(|&index| values[index] == Some(value)).collect();
+                if !cells.is_empty() {
+                    text.push_str(&tr_args(key, &[("cells", &format_cells(&cells))]));
+                    text.push('\n');
+                }
+            }
+            text
+        }
+    }
+}
+
+/// The board with every empty cell that would complete a line marked for the side it wins for, `x`, `o` or
+/// `*` for both, in the board's theme. The styles without a grid list the cells instead.
+pub fn threat_map(board: &Board) -> String {
+    let threats = [Player::X, Player::O].map(|player| winning_cells(board, player));
+    let c = |index: usize| {
+        let players: Vec<Player> = [Player::X, Player::O].into_iter().filter(|&player| threats[player as usize].contains(&index)).collect();
+        if players.is_empty() { cell(board, index) } else { board.theme.threat(&players) }
+    };
+    match board.style {
+        BoardStyle::Pretty => format!("{}{}\n", pretty_grid(board, c), tr("threats.legend")),
+        BoardStyle::Ascii => format!("{}{}\n", ascii_grid(board, c), tr("threats.legend")),
+        BoardStyle::Compact | BoardStyle::Json | BoardStyle::Text => {
+            let mut text = format!("{}", board);
+            for player in [Player::X, Player::O] {
+                if !threats[player as usize].is_empty() {
+                    text.push_str(&tr_args("threats.at", &[("player", &player), ("cells", &format_cells(&threats[player as usize]))]));
+                    text.push('\n');
+                }
+            }
+            text
+        }
+    }
+}
+
+/// A cell of the grids, as wide as the board's scale and numbers make it: a mark is 3 columns at scale 1 and a big
+/// mark of several lines wider at larger scales, and a `--numbers` label is its digit or coordinate with a space
+/// either side, so 3 or 5 columns. `grid` widens every column to its widest cell.
+fn cell(board: &Board, index: usize) -> String {
+    let highlighted = board.highlight.is_some_and(|coord| coord.index() == index);
+    let on_winning_line = board.winning_line().is_some_and(|line| line.contains(&index));
+
+    match board.cells[index] {
+        Some(player) if board.scale > 1 => board.theme.big_mark(player, on_winning_line, board.scale),
+        Some(player) => board.theme.mark(player, on_winning_line),
+        None if highlighted => board.theme.highlight(),
+        None => match cell_number(board.numbers, index) {
+            Some(number) => board.theme.number(&number),
+            None => "   ".to_string(),
+        },
+    }
+}
+
+/// What to type for an empty cell, for `--numbers`. Digits follow `--digits`, and are coordinates when
+/// single digits are off.
+fn cell_number(numbers: CellNumbers, index: usize) -> Option<String> {
+    let coordinate = || Coordinate::from_index(index).map(|coord| coord.to_string());
+    match numbers {
+        CellNumbers::Off => None,
+        CellNumbers::Digits => coordinates::digit_layout().digit(index).map(|digit| digit.to_string()).or_else(coordinate),
+        CellNumbers::Coordinates => coordinate(),
+    }
+}
+
+/// How the grids turn the board for the eye, chosen with `--flip`. The rows and columns keep their
+/// numbers, so moves are typed and saved the same either way.
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Default)]
+pub enum Flip {
+    #[default]
+    None,
+    /// Upside down, row 1 at the bottom.
+    Vertical,
+    /// Mirrored, column 1 on the right.
+    Horizontal,
+    /// Both, the board turned half a turn.
+    Both,
+}
+
+impl Flip {
+    pub const ALL: [Flip; 4] = [Flip::None, Flip::Vertical, Flip::Horizontal, Flip::Both];
+
+    pub fn from_name(name: &str) -> Option<Flip> {
+        Flip::ALL.iter().copied().find(|flip| flip.name() == name.trim().to_lowercase())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flip::None => "none",
+            Flip::Vertical => "vertical",
+            Flip::Horizontal => "horizontal",
+            Flip::Both => "both",
+        }
+    }
+
+    fn reverses_rows(&self) -> bool {
+        matches!(self, Flip::Vertical | Flip::Both)
+    }
+
+    fn reverses_columns(&self) -> bool {
+        matches!(self, Flip::Horizontal | Flip::Both)
+    }
+}
+
+/// What the grids write in empty cells to show what to type there, chosen with `--numbers`.
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Default)]
+pub enum CellNumbers {
+    #[default]
+    Off,
+    /// The digit naming the cell, e.g. `5` for the center.
+    Digits,
+    /// The row and column, e.g. `2-2`.
+    Coordinates,
+}
+
+impl CellNumbers {
+    pub const ALL: [CellNumbers; 3] = [CellNumbers::Off, CellNumbers::Digits, CellNumbers::Coordinates];
+
+    pub fn from_name(name: &str) -> Option<CellNumbers> {
+        CellNumbers::ALL.iter().copied().find(|numbers| numbers.name() == name.trim().to_lowercase())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CellNumbers::Off => "off",
+            CellNumbers::Digits => "digits",
+            CellNumbers::Coordinates => "coordinates",
+        }
+    }
+}
+
+/// Which renderer draws the board, chosen with `--board`.
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Default)]
+pub enum BoardStyle {
+    #[default]
+    Pretty,
+    Ascii,
+    Compact,
+    Json,
+    Text,
+}
+
+impl BoardStyle {
+    pub const ALL: [BoardStyle; 5] = [BoardStyle::Pretty, BoardStyle::Ascii, BoardStyle::Compact, BoardStyle::Json, BoardStyle::Text];
+
+    pub fn from_name(name: &str) -> Option<BoardStyle> {
+        BoardStyle::ALL.iter().copied().find(|style| style.name() == name.trim().to_lowercase())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BoardStyle::Pretty => "pretty",
+            BoardStyle::Ascii => "ascii",
+            BoardStyle::Compact => "compact",
+            BoardStyle::Json => "json",
+            BoardStyle::Text => "text",
+        }
+    }
+
+    pub fn renderer(&self) -> &'static dyn Renderer {
+        match self {
+            BoardStyle::Pretty => &Pretty,
+            BoardStyle::Ascii => &Ascii,
+            BoardStyle::Compact => &Compact,
+            BoardStyle::Json => &Json,
+            BoardStyle::Text => &Accessible,
+        }
+    }
+}
+
+impl Display for BoardStyle {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::display_width;
+
+    #[test]
+    fn emoji_symbols_are_wide() {
+        assert_eq!(display_width("❌"), 2);
+        assert_eq!(display_width("⭕"), 2);
+        assert_eq!(display_width("\x1B[31m❌\x1B[0m"), 2);
+    }
+
+    #[test]
+    fn text_symbols_are_narrow() {
+        assert_eq!(display_width("✓"), 1);
+        assert_eq!(display_width("★"), 1);
+        assert_eq!(display_width(" X "), 3);
+    }
+}
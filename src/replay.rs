@@ -0,0 +1,156 @@
+//! Plays back the games of a self-play file move by move, to look over what the engine did:
+//!
+//! ```text
+//! replay <file> [--game <n>] [--speed <moves per second>] [--threats]
+//! ```
+//!
+//! Enter pauses and resumes, `q` then Enter stops. With `--threats` the empty cells that would complete a
+//! line are marked on the board for the side they win for, as they come and go.
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{GameError, IoError, ParseError};
+use crate::i18n::{tr, tr_args};
+use crate::options::{flag_value, parse_count};
+use crate::render;
+use crate::{get_input_before, get_input_from_console, Board, Coordinate};
+
+/// Moves per second unless `--speed` says otherwise.
+const DEFAULT_SPEED: f64 = 2.0;
+
+/// One game of the file, as the cells played in order.
+pub struct Game {
+    pub number: u64,
+    pub moves: Vec<usize>,
+    /// How long each move took, for files that record it.
+    pub times: Vec<Option<Duration>>,
+}
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut path: Option<&str> = None;
+    let mut only: Option<u64> = None;
+    let mut speed = DEFAULT_SPEED;
+    let mut threats = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--game" => only = Some(parse_count(flag_value(arg, args.next())?)?),
+            "--speed" => speed = parse_speed(flag_value(arg, args.next())?)?,
+            "--threats" => threats = true,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    let path = path.ok_or_else(|| ParseError::new(tr("error.replay_file")))?;
+    let games: Vec<Game> = read_games(path)?
+        .into_iter()
+        .filter(|game| only.is_none_or(|number| game.number == number))
+        .collect();
+    if let Some(number) = only.filter(|_| games.is_empty()) {
+        return Err(ParseError::new(&tr_args("error.no_such_game", &[("game", &number), ("path", &path)])).into());
+    }
+
+    println!("{}", tr("replay.controls"));
+    let frame = Duration::from_secs_f64(1.0 / speed);
+    let show = |board: &Board| if threats { print!("{}", render::threat_map(board)) } else { println!("{}", board) };
+    for game in games {
+        let mut board = Board::new();
+        println!("{}", tr_args("replay.game", &[("game", &game.number)]));
+        show(&board);
+        for (number, &cell) in game.moves.iter().enumerate() {
+            if !wait(frame) {
+                return Ok(());
+            }
+            let coord = Coordinate::from_index(cell)
+                .filter(|&coord| board.get_cell(coord).is_none())
+                .ok_or_else(|| ParseError::new(&tr_args("error.replay_move", &[("game", &game.number), ("path", &path)])))?;
+            let line = match game.times.get(number).copied().flatten() {
+                Some(time) => {
+                    let seconds = format!("{:.1}", time.as_secs_f64());
+                    tr_args("replay.move_time", &[("player", &board.turn), ("move", &coord), ("seconds", &seconds)])
+                }
+                None => tr_args("replay.move", &[("player", &board.turn), ("move", &coord)]),
+            };
+            println!("{}", line);
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+            board.check_if_game_over();
+            show(&board);
+        }
+        if let Some(winner) = board.winner {
+            println!("{}", winner);
+        }
+        if !wait(frame) {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+fn parse_speed(value: &str) -> Result<f64, ParseError> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|speed| speed.is_finite() && *speed > 0.0)
+        .ok_or_else(|| ParseError::new(&tr_args("error.invalid_speed", &[("value", &value)])))
+}
+
+/// Waits one frame. Enter pauses until the next Enter. Returns false if the player quit.
+fn wait(frame: Duration) -> bool {
+    let deadline = Instant::now() + frame;
+    let quits = |line: &str| line.trim().eq_ignore_ascii_case("q");
+    match get_input_before(Some(deadline)) {
+        Ok(None) => true,
+        Ok(Some(line)) if quits(&line) => false,
+        Ok(Some(_)) => {
+            println!("{}", tr("replay.paused"));
+            get_input_from_console().map_or(true, |line| !quits(&line))
+        }
+        // No keyboard to read, e.g. input from a pipe that has ended
+        Err(_) => {
+            thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            true
+        }
+    }
+}
+
+/// Groups the lines of a self-play file into games, keeping their order.
+pub fn read_games(path: &str) -> Result<Vec<Game>, GameError> {
+    let text = fs::read_to_string(path).map_err(|_| IoError::new(&tr_args("error.read_file", &[("path", &path)])))?;
+    Ok(parse_games(&text, path)?)
+}
+
+/// Reads the games out of the text of a self-play file, `path` naming it in errors.
+pub fn parse_games(text: &str, path: &str) -> Result<Vec<Game>, ParseError> {
+    let mut games: Vec<Game> = Vec::new();
+    for (index, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let record = (|| {
+            let number = field(line, "game")?.parse().ok()?;
+            let cell = field(line, "move")?.parse().ok()?;
+            let time = field(line, "time_ms").and_then(|time| time.parse().ok()).map(Duration::from_millis);
+            Some((number, cell, time))
+        })();
+        let (number, cell, time) = record.ok_or_else(|| ParseError::new(&tr_args("error.replay_line", &[("line", &(index + 1)), ("path", &path)])))?;
+        match games.last_mut() {
+            Some(game) if game.number == number => {
+                game.moves.push(cell);
+                game.times.push(time);
+            }
+            _ => games.push(Game { number, moves: vec![cell], times: vec![time] }),
+        }
+    }
+    Ok(games)
+}
+
+/// The value of a key in one of the flat JSON objects written by `selfplay`, without quotes.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let name = format!("\"{}\":", key);
+    let rest = &line[line.find(&name)? + name.len()..];
+    let end = rest.find([',', '}'])?;
+    Some(rest[..end].trim().trim_matches('"'))
+}
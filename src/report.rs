@@ -0,0 +1,60 @@
+//! Everything it takes to set a game up again, bundled for a bug report by the in-game `report` command:
+//!
+//! ```text
+//! # tic-tac-toe 0.1.0 report
+//! version = 0.1.0
+//! position = O../.X./... O
+//! moves = 1-1 2-2
+//! seed = 8230150271650117734
+//! arg.1 = --difficulty
+//! arg.2 = hard
+//! config.theme = mono
+//! ```
+//!
+//! The bundle is in the config file format, so it reads back with `Config::parse`. Playing the moves with
+//! the same arguments, config and seed gives the same game, bot choices included.
+
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::config::Config;
+use crate::error::IoError;
+use crate::i18n::tr_args;
+use crate::info::VERSION;
+use crate::Board;
+
+/// What the game was started with.
+struct Setup {
+    args: Vec<String>,
+    seed: u64,
+}
+
+static SETUP: OnceLock<Setup> = OnceLock::new();
+
+/// Sets the arguments and seed of the game that `bundle` reports on. Can only be set once.
+pub fn start(args: Vec<String>, seed: u64) {
+    let _ = SETUP.set(Setup { args, seed });
+}
+
+/// The report on the game on the board.
+pub fn bundle(board: &Board) -> String {
+    let moves: Vec<String> = board.history[..board.moves].iter().flatten().map(|&(_, coord)| coord.to_string()).collect();
+    let mut text = format!("# tic-tac-toe {} report\nversion = {}\n", VERSION, VERSION);
+    text.push_str(&format!("position = {}\nmoves = {}\n", board.to_compact_string(), moves.join(" ")));
+    if let Some(setup) = SETUP.get() {
+        text.push_str(&format!("seed = {}\n", setup.seed));
+        for (number, arg) in setup.args.iter().enumerate() {
+            text.push_str(&format!("arg.{} = {}\n", number + 1, arg));
+        }
+    }
+    // The file as it is now, which is what the game was set up from unless it was edited since
+    for (key, value) in Config::load().unwrap_or_default().entries() {
+        text.push_str(&format!("config.{} = {}\n", key, value));
+    }
+    text
+}
+
+/// Writes the report on the game to a file.
+pub fn write(board: &Board, path: &str) -> Result<(), IoError> {
+    fs::write(path, bundle(board)).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &path)])))
+}
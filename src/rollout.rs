@@ -0,0 +1,59 @@
+//! Rough odds of each result from random play, for players who find "win, draw or loss with best play"
+//! too abstract. Every rollout plays random moves to the end of the game, so the odds say how the
+//! position tends to go, not what perfect play makes of it.
+
+use std::fmt::{self, Display, Formatter};
+
+use rand::Rng;
+
+use crate::generate::{play, random_game};
+use crate::i18n::tr_args;
+use crate::{Board, Player, Winner};
+
+/// Rollouts per estimate, enough for the percentages to settle within a few points.
+pub const ROLLOUTS: usize = 1000;
+
+/// How often each result came up, as fractions of the rollouts.
+pub struct Estimate {
+    pub o_wins: f64,
+    pub draws: f64,
+    pub x_wins: f64,
+}
+
+/// Plays the position out at random `rollouts` times and counts the results.
+pub fn estimate(board: &Board, rollouts: usize, rng: &mut impl Rng) -> Estimate {
+    let mut wins = [0; 2];
+    let mut draws = 0;
+    for _ in 0..rollouts {
+        let mut end = *board;
+        for coord in random_game(board, rng) {
+            play(&mut end, coord);
+        }
+        match end.winner {
+            Some(Winner::Player(player)) => wins[player as usize] += 1,
+            _ => draws += 1,
+        }
+    }
+    let share = |count: usize| count as f64 / rollouts.max(1) as f64;
+    Estimate { o_wins: share(wins[Player::O as usize]), draws: share(draws), x_wins: share(wins[Player::X as usize]) }
+}
+
+impl Display for Estimate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let percent = |share: f64| format!("{:.0}", 100.0 * share);
+        write!(
+            f,
+            "{}",
+            tr_args(
+                "estimate.odds",
+                &[
+                    ("o", &Player::O.name()),
+                    ("o_wins", &percent(self.o_wins)),
+                    ("draws", &percent(self.draws)),
+                    ("x", &Player::X.name()),
+                    ("x_wins", &percent(self.x_wins)),
+                ],
+            )
+        )
+    }
+}
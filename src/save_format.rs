@@ -0,0 +1,47 @@
+//! The version of the files the game saves and reads back: the game archive, the autosave, player profiles,
+//! the models of how each player plays, and correspondence games. Each starts with the format it was written in:
+//!
+//! ```text
+//! format = 1
+//! ```
+//!
+//! A file in an older format is upgraded as it is read, one version at a time, so that a file written by any
+//! earlier release still loads. A file in a newer format than this build knows is refused rather than read
+//! wrongly. Changing what a file holds means bumping `VERSION` and adding the step from the previous version
+//! to `MIGRATIONS`.
+//!
+//! The formats so far:
+//!
+//! - 0: the files of releases before formats were numbered, without the `format` line.
+//! - 1: the same with the `format` line.
+
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::ParseError;
+use crate::i18n::tr_args;
+
+pub const VERSION: u32 = 1;
+
+/// The steps from each version to the next, taking the text of the file in one to the text in the other.
+const MIGRATIONS: [fn(&str) -> String; VERSION as usize] = [|text| format!("format = 1\n{}", text)];
+
+/// The line every file saved in the current format starts with.
+pub fn header() -> String {
+    format!("format = {}\n", VERSION)
+}
+
+/// The text of a saved file in the current format, upgraded from the one it was written in. An error if it
+/// was written in a newer format, or its format isn't a version at all.
+pub fn upgrade(text: &str, path: &Path) -> Result<String, ParseError> {
+    // The first line past any comments only, as not every file is in the config format after it
+    let first = text.lines().find(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#')).unwrap_or_default();
+    let first = Config::parse(first).unwrap_or_default();
+    let version = match first.get("format") {
+        None => 0,
+        Some(version) => version.parse().ok().filter(|&version| version <= VERSION).ok_or_else(|| {
+            ParseError::new(&tr_args("error.newer_format", &[("path", &path.display()), ("format", &version), ("version", &VERSION)]))
+        })?,
+    };
+    Ok(MIGRATIONS[version as usize..].iter().fold(text.to_string(), |text, migrate| migrate(&text)))
+}
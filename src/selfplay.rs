@@ -0,0 +1,91 @@
+//! Engine-vs-engine games written out as training data, one JSON object per line:
+//!
+//! ```text
+//! {"game": 0, "ply": 2, "position": "....O...X", "to_move": "O", "move": 2, "time_ms": 3, "outcome": "draw", "result": 0}
+//! ```
+//!
+//! - `position`: the 9 cells row by row, `X`, `O` or `.` for empty.
+//! - `to_move`: the side choosing the move.
+//! - `move`: the chosen cell, 0-8 row by row.
+//! - `time_ms`: how long the engine took to choose it, in milliseconds.
+//! - `outcome`: the final result of the game, `X`, `O` or `draw`.
+//! - `result`: the final result from the point of view of `to_move`: 1 win, 0 draw, -1 loss.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::Instant;
+
+use crate::agent::Agent;
+use crate::difficulty::Difficulty;
+use crate::error::{GameError, IoError, ParseError};
+use crate::i18n::tr_args;
+use crate::options::{flag_value, parse_count, parse_difficulty};
+use crate::{bot_seed, cells_key, choose_bot_move, Board, Bot, Player, Winner};
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut games: usize = 100;
+    let mut out = "selfplay.jsonl".to_string();
+    // Perfect play would repeat the same game every time, so both sides blunder now and then by default
+    let mut difficulty = Difficulty::Hard;
+    let mut seed: Option<u64> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => {
+                let value = flag_value(arg, args.next())?;
+                games = value
+                    .parse()
+                    .map_err(|_| ParseError::new(&tr_args("error.invalid_count", &[("value", &value)])))?;
+            }
+            "--out" => out = flag_value(arg, args.next())?.to_string(),
+            "--difficulty" => difficulty = parse_difficulty(flag_value(arg, args.next())?)?,
+            "--seed" => seed = Some(parse_count(flag_value(arg, args.next())?)?),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    let write_error = || IoError::new(&tr_args("error.write_file", &[("path", &out)]));
+    let mut writer = BufWriter::new(File::create(&out).map_err(|_| write_error())?);
+    let agent = Agent::from_difficulty(difficulty);
+    let mut bots = [Bot::new(Player::X, &bot_seed(&agent, Player::X, seed))?, Bot::new(Player::O, &bot_seed(&agent, Player::O, seed))?];
+
+    let mut positions = 0;
+    for game in 0..games {
+        let mut board = Board::new();
+        let mut plies: Vec<(String, Player, usize, u128)> = Vec::new();
+
+        while board.winner.is_none() {
+            let bot = &mut bots[board.turn as usize];
+            let started = Instant::now();
+            let (coord, _) = choose_bot_move(&mut board, bot);
+            plies.push((cells_key(&board.cells), board.turn, coord.index(), started.elapsed().as_millis()));
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+            board.check_if_game_over();
+        }
+
+        let outcome = match board.winner {
+            Some(Winner::Player(player)) => player.to_string(),
+            _ => "draw".to_string(),
+        };
+        for (ply, (position, to_move, cell, time)) in plies.iter().enumerate() {
+            let result = match board.winner {
+                Some(Winner::Player(player)) if player == *to_move => 1,
+                Some(Winner::Player(_)) => -1,
+                _ => 0,
+            };
+            writeln!(
+                writer,
+                "{{\"game\": {}, \"ply\": {}, \"position\": \"{}\", \"to_move\": \"{}\", \"move\": {}, \"time_ms\": {}, \"outcome\": \"{}\", \"result\": {}}}",
+                game, ply, position, to_move, cell, time, outcome, result
+            )
+            .map_err(|_| write_error())?;
+        }
+        positions += plies.len();
+    }
+    writer.flush().map_err(|_| write_error())?;
+
+    println!("{}", tr_args("selfplay.done", &[("positions", &positions), ("games", &games), ("path", &out)]));
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Short codes for positions and games, safe to put in a URL, printed with a QR code to scan them off the
+//! screen:
+//!
+//! ```text
+//! share [<position or code>] [--game <id>]
+//! ```
+//!
+//! A position is its nine cells in reading order, `o.x.o..x.`, the side to move following from the marks.
+//! A game is the position it started from, `_`, then the cells played as digits 1 to 9 in reading order:
+//! `........._5193`. Given a code, `share` shows what it holds.
+
+use crate::archive;
+use crate::error::{GameError, ParseError};
+use crate::generate::play;
+use crate::i18n::{tr, tr_args};
+use crate::options::flag_value;
+use crate::qr;
+use crate::{Board, Coordinate, Player};
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut code: Option<&str> = None;
+    let mut game: Option<&str> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--game" => game = Some(flag_value(arg, args.next())?),
+            _ if code.is_none() && !arg.starts_with("--") => code = Some(arg),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    let (start, moves) = match (code, game) {
+        (Some(_), Some(_)) => return Err(ParseError::new(tr("error.share_usage")).into()),
+        (_, Some(id)) => {
+            let game = archive::find(id)?;
+            (game.start_board()?, game.moves)
+        }
+        (Some(code), None) => decode(code)?,
+        (None, None) => (Board::new(), Vec::new()),
+    };
+
+    let mut board = start;
+    for &coord in &moves {
+        play(&mut board, coord);
+    }
+    let code = if moves.is_empty() { position_code(&board) } else { game_code(&start, &moves) };
+    println!("{}", board);
+    if !moves.is_empty() {
+        let moves: Vec<String> = moves.iter().map(Coordinate::to_string).collect();
+        println!("{}", tr_args("share.moves", &[("moves", &moves.join(" "))]));
+    }
+    println!("{}", tr_args("share.code", &[("code", &code)]));
+    if let Some(modules) = qr::encode(&code) {
+        print!("{}", qr::render(&modules));
+    }
+    Ok(())
+}
+
+/// The cells of the position, e.g. `o.x.o..x.`.
+pub fn position_code(board: &Board) -> String {
+    board.cells.iter().map(|cell| cell.map_or('.', |player| if player == Player::X { 'x' } else { 'o' })).collect()
+}
+
+pub fn game_code(start: &Board, moves: &[Coordinate]) -> String {
+    let cells: String = moves.iter().map(|coord| char::from(b'1' + coord.index() as u8)).collect();
+    format!("{}_{}", position_code(start), cells)
+}
+
+/// The position a code starts from and the moves played from it, none for the code of a position. Also
+/// reads positions written for `--position`.
+pub fn decode(code: &str) -> Result<(Board, Vec<Coordinate>), ParseError> {
+    let (position, cells) = code.trim().split_once('_').unwrap_or((code, ""));
+    let start = Board::from_compact(position)?;
+    let invalid = || ParseError::new(&tr_args("error.share_code", &[("code", &code)]));
+    let mut board = start;
+    let mut moves = Vec::new();
+    for digit in cells.chars() {
+        let coord = digit
+            .to_digit(10)
+            .and_then(|digit| Coordinate::from_index((digit as usize).wrapping_sub(1)))
+            .ok_or_else(invalid)?;
+        if board.winner.is_some() || board.get_cell(coord).is_some() {
+            return Err(invalid());
+        }
+        play(&mut board, coord);
+        moves.push(coord);
+    }
+    Ok((start, moves))
+}
@@ -0,0 +1,317 @@
+//! Reports over many games, from the game archive or a self-play file:
+//!
+//! ```text
+//! stats openings [--player <name>] [--file <selfplay file>]
+//! stats accuracy [--player <name>]
+//! leaderboard [--difficulty <level>]
+//! ```
+//!
+//! `openings` groups the games by first move and first reply, counting rotations and reflections of the board
+//! as the same opening, and shows how often the first player won, drew and lost each of them.
+//!
+//! `accuracy` shows how well each human played against the bots, on average and lately, or game by game
+//! for one player.
+//!
+//! `leaderboard` ranks the named humans by their score against each difficulty of the bot.
+
+use std::collections::BTreeMap;
+
+use crate::archive::{self, format_date, SavedGame};
+use crate::difficulty::Difficulty;
+use crate::error::{GameError, ParseError};
+use crate::i18n::{tr, tr_args};
+use crate::options::{flag_value, parse_difficulty};
+use crate::replay::read_games;
+use crate::{Board, Coordinate, Player, Winner};
+
+/// Results of the games that reached an opening, from the first player's side.
+#[derive(Debug, Default, Clone, Copy)]
+struct Tally {
+    wins: usize,
+    draws: usize,
+    losses: usize,
+}
+
+impl Tally {
+    fn add(&mut self, outcome: Option<bool>) {
+        match outcome {
+            Some(true) => self.wins += 1,
+            Some(false) => self.losses += 1,
+            None => self.draws += 1,
+        }
+    }
+
+    fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Points per game, a win counting 1 and a draw half.
+    fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64
+    }
+}
+
+/// The start of a game that began on the empty board: the cells played first and second, and
+/// whether the first player went on to win (None for a draw).
+struct Opening {
+    first: usize,
+    reply: Option<usize>,
+    outcome: Option<bool>,
+}
+
+/// Games averaged for the recent accuracy.
+const RECENT_GAMES: usize = 10;
+
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let report = args.first().map(String::as_str);
+    if report != Some("openings") && report != Some("accuracy") {
+        return Err(ParseError::new(tr("error.stats_usage")).into());
+    }
+    let mut player: Option<String> = None;
+    let mut file: Option<String> = None;
+
+    let mut args = args[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--player" => player = Some(flag_value(arg, args.next())?.to_string()),
+            "--file" => file = Some(flag_value(arg, args.next())?.to_string()),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    if report == Some("accuracy") {
+        if file.is_some() {
+            return Err(ParseError::new(tr("error.stats_usage")).into());
+        }
+        return print_accuracy(player.as_deref());
+    }
+
+    let openings = match &file {
+        Some(path) => selfplay_openings(path)?,
+        None => archive_openings(player.as_deref())?,
+    };
+    print_openings(&openings);
+    Ok(())
+}
+
+/// Saved games that began on the empty board, only those of `player` if one is given.
+fn archive_openings(player: Option<&str>) -> Result<Vec<Opening>, GameError> {
+    let first_player = Board::new().turn;
+    Ok(archive::load()?
+        .iter()
+        .filter(|game| game.start.is_none())
+        .filter(|game| player.is_none_or(|player| game.players.iter().any(|name| name.eq_ignore_ascii_case(player))))
+        .filter_map(|game| {
+            Some(Opening {
+                first: game.moves.first()?.index(),
+                reply: game.moves.get(1).map(Coordinate::index),
+                outcome: won_by(game.winner, first_player),
+            })
+        })
+        .collect())
+}
+
+/// Games of a self-play file, played out to find their results.
+fn selfplay_openings(path: &str) -> Result<Vec<Opening>, GameError> {
+    let mut openings = Vec::new();
+    for game in read_games(path)? {
+        let mut board = Board::new();
+        let first_player = board.turn;
+        for coord in game.moves.iter().filter_map(|&cell| Coordinate::from_index(cell)) {
+            board.play_move(coord);
+            board.turn = board.turn.opponent();
+            board.check_if_game_over();
+        }
+        if let (Some(winner), Some(&first)) = (board.winner, game.moves.first()) {
+            openings.push(Opening { first, reply: game.moves.get(1).copied(), outcome: won_by(winner, first_player) });
+        }
+    }
+    Ok(openings)
+}
+
+/// One line per human with the average accuracy over all their saved games and the last few,
+/// or every game of one player.
+fn print_accuracy(player: Option<&str>) -> Result<(), GameError> {
+    let mut players: BTreeMap<String, Vec<(SavedGame, f64)>> = BTreeMap::new();
+    for game in archive::load()? {
+        if let (Some(human), Some(accuracy)) = (game.human, game.accuracy) {
+            let name = game.players[human as usize].clone();
+            if player.is_none_or(|player| name.eq_ignore_ascii_case(player)) {
+                players.entry(name).or_default().push((game, accuracy));
+            }
+        }
+    }
+    if players.is_empty() {
+        println!("{}", tr("stats.no_games"));
+    }
+
+    let average = |games: &[(SavedGame, f64)]| format!("{:.0}", games.iter().map(|(_, accuracy)| accuracy).sum::<f64>() / games.len() as f64);
+    for (name, games) in &players {
+        let recent = &games[games.len().saturating_sub(RECENT_GAMES)..];
+        println!(
+            "{}",
+            tr_args(
+                "stats.accuracy",
+                &[("player", name), ("games", &games.len()), ("average", &average(games)), ("recent", &recent.len()), ("recent_average", &average(recent))],
+            )
+        );
+        if player.is_some() {
+            for (game, accuracy) in games {
+                println!("  #{:<5}{}  {:>4.0}%", game.id, format_date(game.started), accuracy);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_leaderboard(args: &[String]) -> Result<(), GameError> {
+    let mut only: Option<Difficulty> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--difficulty" => only = Some(parse_difficulty(flag_value(arg, args.next())?)?),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    // Players without a name play as their mark and have no profile, so they are left out, and so are games
+    // edited by hand
+    let (games, edited): (Vec<SavedGame>, Vec<SavedGame>) = archive::load()?.into_iter().partition(|game| game.is_intact() != Some(false));
+    let mut printed = false;
+    for difficulty in Difficulty::ALL.into_iter().filter(|difficulty| only.is_none_or(|only| only == *difficulty)) {
+        let mut players: BTreeMap<&str, Tally> = BTreeMap::new();
+        for game in games.iter().filter(|game| game.difficulty == Some(difficulty)) {
+            let Some(human) = game.human else {
+                continue;
+            };
+            let name = game.players[human as usize].as_str();
+            if name != human.to_string() {
+                players.entry(name).or_default().add(won_by(game.winner, human));
+            }
+        }
+        if players.is_empty() {
+            continue;
+        }
+
+        let mut ranking: Vec<(&str, Tally)> = players.into_iter().collect();
+        ranking.sort_by(|(_, a), (_, b)| b.score().total_cmp(&a.score()).then(b.games().cmp(&a.games())));
+        if printed {
+            println!();
+        }
+        println!("{}", tr_args("leaderboard.difficulty", &[("difficulty", &difficulty)]));
+        println!(
+            "{:<4}{:<16}{:>7}{:>6}{:>6}{:>6}{:>7}",
+            "",
+            tr("leaderboard.player"),
+            tr("stats.games"),
+            tr("stats.wins"),
+            tr("stats.draws"),
+            tr("stats.losses"),
+            tr("leaderboard.score")
+        );
+        for (rank, (name, tally)) in ranking.iter().enumerate() {
+            println!(
+                "{:<4}{:<16}{:>7}{:>6}{:>6}{:>6}{:>6.0}%",
+                format!("{}.", rank + 1),
+                name,
+                tally.games(),
+                tally.wins,
+                tally.draws,
+                tally.losses,
+                100.0 * tally.score()
+            );
+        }
+        printed = true;
+    }
+
+    if !edited.is_empty() {
+        println!("{}", tr_args("leaderboard.edited", &[("count", &edited.len())]));
+    }
+    if printed {
+        println!("{}", tr("leaderboard.legend"));
+    } else {
+        println!("{}", tr("stats.no_games"));
+    }
+    Ok(())
+}
+
+fn won_by(winner: Winner, player: Player) -> Option<bool> {
+    match winner {
+        Winner::Player(winner) => Some(winner == player),
+        Winner::Draw => None,
+    }
+}
+
+fn print_openings(openings: &[Opening]) {
+    if openings.is_empty() {
+        println!("{}", tr("stats.no_games"));
+        return;
+    }
+
+    let mut firsts: BTreeMap<usize, Tally> = BTreeMap::new();
+    let mut replies: BTreeMap<(usize, usize), Tally> = BTreeMap::new();
+    for opening in openings {
+        let (first, reply) = canonical(opening.first, opening.reply);
+        firsts.entry(first).or_default().add(opening.outcome);
+        if let Some(reply) = reply {
+            replies.entry((first, reply)).or_default().add(opening.outcome);
+        }
+    }
+
+    println!(
+        "{:<16}{:>7}{:>8}{:>8}{:>8}",
+        tr("stats.opening"),
+        tr("stats.games"),
+        tr("stats.wins"),
+        tr("stats.draws"),
+        tr("stats.losses")
+    );
+    for (&first, tally) in &firsts {
+        let kind = match first {
+            4 => tr("stats.center"),
+            0 => tr("stats.corner"),
+            _ => tr("stats.edge"),
+        };
+        print_row(&format!("{} {}", cell_name(first), kind), tally);
+        for (&(_, reply), tally) in replies.range((first, 0)..(first + 1, 0)) {
+            print_row(&format!("  {} {}", cell_name(first), cell_name(reply)), tally);
+        }
+    }
+    println!("{}", tr("stats.legend"));
+}
+
+fn print_row(label: &str, tally: &Tally) {
+    let percent = |count: usize| format!("{:.0}%", 100.0 * count as f64 / tally.games() as f64);
+    println!("{:<16}{:>7}{:>8}{:>8}{:>8}", label, tally.games(), percent(tally.wins), percent(tally.draws), percent(tally.losses));
+}
+
+fn cell_name(index: usize) -> String {
+    Coordinate::from_index(index).map_or_else(String::new, |coord| coord.to_string())
+}
+
+/// Turns an opening into the same one for every rotation and reflection of the board. The first move
+/// lands on 1-1, 1-2 or 2-2, and the reply on the earliest cell it can reach from there.
+fn canonical(first: usize, reply: Option<usize>) -> (usize, Option<usize>) {
+    let mut board = Board::new();
+    board.cells[first] = Some(Player::O);
+    let first = board
+        .generate_all_equivalent_states()
+        .iter()
+        .filter_map(|cells| cells.iter().position(|cell| cell.is_some()))
+        .min()
+        .unwrap_or(first);
+    let Some(reply) = reply else {
+        return (first, None);
+    };
+
+    board.cells[reply] = Some(Player::X);
+    let reply = board
+        .generate_all_equivalent_states()
+        .iter()
+        .filter(|cells| cells[first] == Some(Player::O))
+        .filter_map(|cells| cells.iter().position(|&cell| cell == Some(Player::X)))
+        .min()
+        .unwrap_or(reply);
+    (first, Some(reply))
+}
@@ -0,0 +1,38 @@
+use std::fmt::{self, Display, Formatter};
+
+/// How the bot chooses between moves.
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Default)]
+pub enum Strategy {
+    /// Plain minimax with alpha-beta pruning.
+    #[default]
+    Minimax,
+    /// Among the moves minimax rates equally, steers towards positions where the current player has blundered before.
+    Exploiter,
+    /// Maximizes the expected score against an opponent who sometimes plays at random, instead of the worst case.
+    Expectimax,
+    /// Asks a small neural network loaded from a weights file, one move deep.
+    Neural,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [Strategy::Minimax, Strategy::Exploiter, Strategy::Expectimax, Strategy::Neural];
+
+    pub fn from_name(name: &str) -> Option<Strategy> {
+        Strategy::ALL.iter().copied().find(|strategy| strategy.name() == name.trim().to_lowercase())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Minimax => "minimax",
+            Strategy::Exploiter => "exploiter",
+            Strategy::Expectimax => "expectimax",
+            Strategy::Neural => "neural",
+        }
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,85 @@
+//! SVG pictures of positions, for blog posts and slides. `export-svg` writes them from the game or the command line:
+//!
+//! ```text
+//! export-svg <position> [--out <file>]
+//! ```
+
+use std::fs;
+
+use crate::error::{GameError, IoError, ParseError};
+use crate::i18n::tr_args;
+use crate::options::flag_value;
+use crate::{Board, Player};
+
+const CELL: usize = 100;
+const MARGIN: usize = 10;
+
+/// Draws the grid, the marks and a stroke through the winning line, if there is one.
+pub fn render(board: &Board) -> String {
+    let size = CELL * 3 + MARGIN * 2;
+    let center = |index: usize| (MARGIN + (index % 3) * CELL + CELL / 2, MARGIN + (index / 3) * CELL + CELL / 2);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {size} {size}\">\n\
+         <rect width=\"{size}\" height=\"{size}\" fill=\"white\"/>\n"
+    );
+    for i in 1..3 {
+        let offset = MARGIN + i * CELL;
+        let end = size - MARGIN;
+        svg.push_str(&format!("<line x1=\"{offset}\" y1=\"{MARGIN}\" x2=\"{offset}\" y2=\"{end}\" stroke=\"black\" stroke-width=\"4\"/>\n"));
+        svg.push_str(&format!("<line x1=\"{MARGIN}\" y1=\"{offset}\" x2=\"{end}\" y2=\"{offset}\" stroke=\"black\" stroke-width=\"4\"/>\n"));
+    }
+
+    for (index, cell) in board.cells.iter().enumerate() {
+        let (x, y) = center(index);
+        let reach = CELL * 3 / 10;
+        match cell {
+            Some(Player::X) => {
+                let stroke = "stroke=\"#d62728\" stroke-width=\"10\" stroke-linecap=\"round\"";
+                svg.push_str(&format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {stroke}/>\n", x - reach, y - reach, x + reach, y + reach));
+                svg.push_str(&format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {stroke}/>\n", x - reach, y + reach, x + reach, y - reach));
+            }
+            Some(Player::O) => {
+                svg.push_str(&format!("<circle cx=\"{x}\" cy=\"{y}\" r=\"{reach}\" fill=\"none\" stroke=\"#1f77b4\" stroke-width=\"10\"/>\n"));
+            }
+            None => {}
+        }
+    }
+
+    if let Some(line) = board.winning_line() {
+        let ((x1, y1), (x2, y2)) = (center(line[0]), center(line[2]));
+        svg.push_str(&format!(
+            "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"#333333\" stroke-width=\"12\" stroke-linecap=\"round\" opacity=\"0.8\"/>\n"
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn write(board: &Board, path: &str) -> Result<(), IoError> {
+    fs::write(path, render(board)).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &path)])))
+}
+
+/// The `export-svg` subcommand.
+pub fn run(args: &[String]) -> Result<(), GameError> {
+    let mut position: Option<&str> = None;
+    let mut out = "position.svg".to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = flag_value(arg, args.next())?.to_string(),
+            _ if position.is_none() && !arg.starts_with("--") => position = Some(arg),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    let board = match position {
+        Some(position) => Board::from_compact(position)?,
+        None => Board::new(),
+    };
+    write(&board, &out)?;
+    println!("{}", tr_args("svg.written", &[("path", &out)]));
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::Player;
+
+const RESET: &str = "\x1B[0m";
+
+/// The marks at scales 2 and 3, drawn with their own letter.
+const BIG_X: [&[&str]; 2] = [
+    &["X   X", "  X  ", "X   X"],
+    &["X       X", "  X   X  ", "    X    ", "  X   X  ", "X       X"],
+];
+const BIG_O: [&[&str]; 2] = [
+    &[" OOO ", "O   O", " OOO "],
+    &["  OOOOO  ", " O     O ", " O     O ", " O     O ", "  OOOOO  "],
+];
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Default,
+    HighContrast,
+    ColorBlind,
+    Monochrome,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::Default, Theme::HighContrast, Theme::ColorBlind, Theme::Monochrome];
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name.trim().to_lowercase().as_str() {
+            "default" => Some(Theme::Default),
+            "high-contrast" | "contrast" => Some(Theme::HighContrast),
+            "color-blind" | "colorblind" => Some(Theme::ColorBlind),
+            "monochrome" | "mono" => Some(Theme::Monochrome),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::HighContrast => "high-contrast",
+            Theme::ColorBlind => "color-blind",
+            Theme::Monochrome => "monochrome",
+        }
+    }
+
+    /// Renders a placed mark as a 3-column cell.
+    pub fn mark(&self, player: Player, on_winning_line: bool) -> String {
+        match self {
+            Theme::Monochrome => {
+                // No color at all, so the shapes and the `=` on either side of marks on the winning line do the work.
+                let shape = match player {
+                    Player::X => "X",
+                    Player::O => "O",
+                };
+                if on_winning_line { format!("={}=", shape) } else { format!(" {} ", shape) }
+            }
+            _ => format!("\x1B[{}{}m {} {}", self.player_style(player), self.line_style(on_winning_line), player, RESET),
+        }
+    }
+
+    /// Renders a placed mark in big letters, as many lines as the scale needs. Monochrome fills the blanks
+    /// of marks on the winning line with dots instead of coloring them.
+    pub fn big_mark(&self, player: Player, on_winning_line: bool, scale: usize) -> String {
+        let glyphs = match player {
+            Player::X => BIG_X,
+            Player::O => BIG_O,
+        };
+        let glyph = glyphs[scale.clamp(2, glyphs.len() + 1) - 2];
+        let lines: Vec<String> = glyph
+            .iter()
+            .map(|line| match self {
+                Theme::Monochrome if on_winning_line => line.replace(' ', "."),
+                Theme::Monochrome => line.to_string(),
+                _ => format!("\x1B[{}{}m {} {}", self.player_style(player), self.line_style(on_winning_line), line, RESET),
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    /// The extra ANSI style of marks on the winning line.
+    fn line_style(&self, on_winning_line: bool) -> &'static str {
+        match self {
+            _ if !on_winning_line => "",
+            Theme::HighContrast => ";7",
+            Theme::ColorBlind => ";4",
+            _ => ";42",
+        }
+    }
+
+    /// The color of a player's marks, as an ANSI style.
+    fn player_style(&self, player: Player) -> &'static str {
+        match (self, player) {
+            (Theme::HighContrast, Player::X) => "1;97;40",
+            (Theme::HighContrast, Player::O) => "1;93;40",
+            // Okabe-Ito orange and sky blue, distinguishable under all common forms of color blindness.
+            (Theme::ColorBlind, Player::X) => "1;38;5;208",
+            (Theme::ColorBlind, Player::O) => "1;38;5;39",
+            (_, Player::X) => "31",
+            (_, Player::O) => "34",
+        }
+    }
+
+    /// Renders an empty cell that would complete a line: the small mark of the player it wins for, in
+    /// their color, or `*` when it wins for both.
+    pub fn threat(&self, players: &[Player]) -> String {
+        let (symbol, style) = match players {
+            [player] => (player.to_string().to_lowercase(), self.player_style(*player)),
+            _ => ("*".to_string(), "1;33"),
+        };
+        match self {
+            Theme::Monochrome => format!(" {} ", symbol),
+            _ => format!("\x1B[{}m {} {}", style, symbol, RESET),
+        }
+    }
+
+    /// Renders the highlighted empty cell, i.e. the one the player is about to confirm.
+    pub fn highlight(&self) -> String {
+        match self {
+            Theme::Default => format!("\x1B[33m[ ]{}", RESET),
+            Theme::HighContrast => format!("\x1B[1;97m[ ]{}", RESET),
+            Theme::ColorBlind => format!("\x1B[1;38;5;220m[ ]{}", RESET),
+            Theme::Monochrome => "[ ]".to_string(),
+        }
+    }
+
+    /// Renders what to type for an empty cell, faint so that it doesn't pass for a mark.
+    pub fn number(&self, text: &str) -> String {
+        match self {
+            Theme::Monochrome => format!(" {} ", text),
+            Theme::HighContrast => format!("\x1B[37m {} {}", text, RESET),
+            _ => format!("\x1B[2m {} {}", text, RESET),
+        }
+    }
+
+    /// Renders an empty cell as the result of playing there for the side to move: `+` a win,
+    /// `=` a draw, `-` a loss.
+    pub fn evaluation(&self, value: i32) -> String {
+        let symbol = match value.signum() {
+            1 => "+",
+            0 => "=",
+            _ => "-",
+        };
+        let style = match (self, value.signum()) {
+            (Theme::Monochrome, _) => return format!(" {} ", symbol),
+            (Theme::HighContrast, 1) => "1;92",
+            (Theme::HighContrast, 0) => "1;93",
+            (Theme::HighContrast, _) => "1;91",
+            // Okabe-Ito bluish green, yellow and vermillion
+            (Theme::ColorBlind, 1) => "1;38;5;36",
+            (Theme::ColorBlind, 0) => "1;38;5;220",
+            (Theme::ColorBlind, _) => "1;38;5;166",
+            (_, 1) => "32",
+            (_, 0) => "33",
+            (_, _) => "31",
+        };
+        format!("\x1B[{}m {} {}", style, symbol, RESET)
+    }
+}
+
+impl Display for Theme {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,460 @@
+//! Tournaments between bots, and gauntlets to check one bot against the same opponents over time:
+//!
+//! ```text
+//! swiss [--agents <name,name,...>] [--rounds <n>] [--seed <n>] [--out <file>] [--export <file>] [--workers <n>]
+//! gauntlet <agent> [--opponents <name,name,...>] [--games <n>] [--seed <n>] [--baseline <file>] [--tolerance <score>] [--save] [--export <file>] [--workers <n>]
+//! ```
+//!
+//! `--export` writes every game and the final scores as CSV or JSON, see `export`.
+//!
+//! The games of a round, or of a whole gauntlet, are played at the same time by `--workers` threads, one per
+//! core by default. Every game is seeded from `--seed` and its place in the run rather than by the thread that
+//! plays it, and results are kept in that order, so the number of workers doesn't change the outcome. The
+//! exception is agents with `time_ms`: how deep they get before their time is up depends on how busy the
+//! machine is, and so do the moves they play. With any of them in the pool the games are played one at a
+//! time unless `--workers` says otherwise, which still doesn't make them repeatable.
+//!
+//! In `swiss`, the agents are those of the config file and the difficulties, all of them unless `--agents` picks some.
+//! Each round pairs bots with the same score or close to it that haven't met yet, the leaders first, and each
+//! pair plays two games, one with each side to start. A win scores 1 and a draw half. With an odd number of
+//! bots the lowest one without a bye so far sits the round out and scores 1, a drawn match. The standings rank
+//! by score, then by Buchholz, the total score of the opponents met.
+//!
+//! `--out` writes the results round by round in the format of the config file:
+//!
+//! ```text
+//! [round.1.1]
+//! first = hard
+//! second = easy
+//! score = 1.5-0.5
+//! ```
+//!
+//! `gauntlet` plays the agent against each opponent, the difficulties unless `--opponents` names others, and
+//! compares its score against each, the share of the points it took, with the baseline file. A score lower
+//! than the baseline by more than the tolerance is a regression, and the command fails so that a script or CI
+//! job running it fails too. `--save` writes the scores as the new baseline instead:
+//!
+//! ```text
+//! agent = hard
+//! games = 20
+//! seed = 0
+//! score.easy = 0.95
+//! score.perfect = 0.40
+//! ```
+
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+
+use crate::agent::{parse_seat, Agent, Seat};
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::error::{GameError, IoError, ParseError};
+use crate::export::{self, Table, Value};
+use crate::generate::play;
+use crate::i18n::{tr, tr_args};
+use crate::options::{flag_value, parse_count, parse_probability, Options};
+use crate::{bot_seed, choose_bot_move, Board, Bot, Coordinate, Player, Winner};
+
+/// Points for sitting a round out.
+const BYE_SCORE: f64 = 1.0;
+
+const DEFAULT_GAUNTLET_GAMES: u64 = 20;
+const DEFAULT_BASELINE: &str = "gauntlet.baseline";
+/// How far below the baseline a score may fall before it counts as a regression, for the luck of the draw.
+const DEFAULT_TOLERANCE: f64 = 0.05;
+
+struct Entrant {
+    agent: Agent,
+    score: f64,
+    /// Indexes of the entrants met so far.
+    opponents: Vec<usize>,
+    had_bye: bool,
+}
+
+/// A game played, for `--export`.
+struct GameRecord {
+    round: Option<usize>,
+    o: String,
+    x: String,
+    winner: Winner,
+    moves: Vec<Coordinate>,
+    seed: u64,
+}
+
+/// A pairing of a round: the two entrants and their scores over its games, or a bye.
+struct Pairing {
+    first: usize,
+    second: Option<usize>,
+    scores: (f64, f64),
+}
+
+pub fn run_swiss(args: &[String]) -> Result<(), GameError> {
+    let mut names: Option<Vec<String>> = None;
+    let mut rounds: Option<usize> = None;
+    let mut seed = 0;
+    let mut out: Option<String> = None;
+    let mut export_path: Option<String> = None;
+    let mut workers: Option<usize> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--agents" => names = Some(flag_value(arg, args.next())?.split(',').map(|name| name.trim().to_string()).collect()),
+            "--rounds" => rounds = Some(parse_count(flag_value(arg, args.next())?)? as usize),
+            "--seed" => seed = parse_count(flag_value(arg, args.next())?)?,
+            "--out" => out = Some(flag_value(arg, args.next())?.to_string()),
+            "--export" => export_path = Some(flag_value(arg, args.next())?.to_string()),
+            "--workers" => workers = Some(parse_count(flag_value(arg, args.next())?)?.max(1) as usize),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+
+    let mut entrants: Vec<Entrant> = agents(names)?
+        .into_iter()
+        .map(|agent| Entrant { agent, score: 0.0, opponents: Vec::new(), had_bye: false })
+        .collect();
+    if entrants.len() < 2 {
+        return Err(ParseError::new(tr("error.tournament_agents")).into());
+    }
+    let workers = workers.unwrap_or_else(|| default_workers(entrants.iter().map(|entrant| &entrant.agent)));
+    // Enough rounds for a single leader to emerge, as in a knockout
+    let rounds = rounds.unwrap_or_else(|| entrants.len().next_power_of_two().trailing_zeros() as usize);
+
+    let mut results: Vec<Vec<Pairing>> = Vec::new();
+    let mut records = Vec::new();
+    for round in 1..=rounds {
+        println!("{}", tr_args("tournament.round", &[("round", &round)]));
+        let mut pairings = pair(&entrants);
+        let mut jobs = Vec::new();
+        for pairing in &pairings {
+            if let Some(second) = pairing.second {
+                let games_seed = seed.wrapping_add((records.len() + jobs.len()) as u64 * 2);
+                jobs.extend(match_games(&entrants[pairing.first].agent, &entrants[second].agent, 2, games_seed, Some(round)));
+            }
+        }
+        let mut played = play_games(jobs, workers)?.into_iter();
+        for pairing in &mut pairings {
+            let Some(second) = pairing.second else {
+                entrants[pairing.first].score += BYE_SCORE;
+                entrants[pairing.first].had_bye = true;
+                pairing.scores = (BYE_SCORE, 0.0);
+                println!("  {}", tr_args("tournament.bye", &[("agent", &entrants[pairing.first].agent.name)]));
+                continue;
+            };
+            let games: Vec<GameRecord> = played.by_ref().take(2).collect();
+            pairing.scores = match_scores(&games);
+            records.extend(games);
+            println!(
+                "  {:<16} {} - {} {}",
+                entrants[pairing.first].agent.name, pairing.scores.0, pairing.scores.1, entrants[second].agent.name
+            );
+            entrants[pairing.first].score += pairing.scores.0;
+            entrants[second].score += pairing.scores.1;
+            entrants[pairing.first].opponents.push(second);
+            entrants[second].opponents.push(pairing.first);
+        }
+        results.push(pairings);
+    }
+
+    println!();
+    print_standings(&entrants);
+    if let Some(path) = out {
+        fs::write(&path, to_config(&entrants, &results)).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &path)])))?;
+        println!("{}", tr_args("tournament.written", &[("path", &path)]));
+    }
+    if let Some(path) = export_path {
+        let columns = vec!["rank", "agent", "score", "buchholz", "byes"];
+        let rows = ranking(&entrants)
+            .iter()
+            .enumerate()
+            .map(|(rank, &index)| {
+                let entrant = &entrants[index];
+                vec![
+                    Value::Number((rank + 1) as f64),
+                    Value::Text(entrant.agent.name.clone()),
+                    Value::Number(entrant.score),
+                    Value::Number(buchholz(&entrants, index)),
+                    Value::Number(u8::from(entrant.had_bye) as f64),
+                ]
+            })
+            .collect();
+        write_export(&path, &records, &Table { columns, rows })?;
+    }
+    Ok(())
+}
+
+pub fn run_gauntlet(args: &[String]) -> Result<(), GameError> {
+    let mut agent: Option<String> = None;
+    let mut opponents: Option<Vec<String>> = None;
+    let mut games = DEFAULT_GAUNTLET_GAMES;
+    let mut seed = 0;
+    let mut baseline_path = DEFAULT_BASELINE.to_string();
+    let mut tolerance = DEFAULT_TOLERANCE;
+    let mut save = false;
+    let mut export_path: Option<String> = None;
+    let mut workers: Option<usize> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--opponents" => opponents = Some(flag_value(arg, args.next())?.split(',').map(|name| name.trim().to_string()).collect()),
+            "--games" => games = parse_count(flag_value(arg, args.next())?)?.max(1),
+            "--seed" => seed = parse_count(flag_value(arg, args.next())?)?,
+            "--baseline" => baseline_path = flag_value(arg, args.next())?.to_string(),
+            "--tolerance" => tolerance = parse_probability(flag_value(arg, args.next())?)?,
+            "--save" => save = true,
+            "--export" => export_path = Some(flag_value(arg, args.next())?.to_string()),
+            "--workers" => workers = Some(parse_count(flag_value(arg, args.next())?)?.max(1) as usize),
+            name if agent.is_none() && !name.starts_with("--") => agent = Some(name.to_string()),
+            _ => return Err(ParseError::new(&tr_args("error.unknown_argument", &[("arg", arg)])).into()),
+        }
+    }
+    let agent = agent.ok_or_else(|| ParseError::new(tr("error.gauntlet_usage")))?;
+    let agent = agents(Some(vec![agent]))?.remove(0);
+    let opponents: Vec<Agent> = agents(opponents)?.into_iter().filter(|opponent| opponent.name != agent.name).collect();
+    let workers = workers.unwrap_or_else(|| default_workers(opponents.iter().chain([&agent])));
+    let baseline = fs::read_to_string(&baseline_path).ok().map(|text| Config::parse(&text)).transpose()?;
+    if let Some(other) = baseline.as_ref().and_then(|baseline| baseline.get("agent")).filter(|&other| other != agent.name && !save) {
+        println!("{}", tr_args("gauntlet.other_agent", &[("path", &baseline_path), ("agent", &other)]));
+    }
+
+    let jobs = opponents
+        .iter()
+        .enumerate()
+        .flat_map(|(number, opponent)| match_games(&agent, opponent, games, seed.wrapping_add(number as u64 * games * 2), None))
+        .collect();
+    let records = play_games(jobs, workers)?;
+
+    let mut scores = Vec::new();
+    let mut summary = Vec::new();
+    let mut regressions = 0;
+    println!("{:<16}{:>7}{:>10}", tr("tournament.opponent"), tr("tournament.score"), tr("tournament.baseline"));
+    for (opponent, played) in opponents.iter().zip(records.chunks(games as usize)) {
+        let (points, _) = match_scores(played);
+        let score = points / games as f64;
+        let expected = baseline.as_ref().and_then(|baseline| baseline.get(&format!("score.{}", opponent.name))?.parse::<f64>().ok());
+        let regressed = !save && expected.is_some_and(|expected| score < expected - tolerance);
+        summary.push(vec![
+            Value::Text(agent.name.clone()),
+            Value::Text(opponent.name.clone()),
+            Value::Number(games as f64),
+            Value::Number(score),
+            expected.map_or(Value::Missing, Value::Number),
+            Value::Number(u8::from(regressed) as f64),
+        ]);
+        let expected = expected.map_or("-".to_string(), |expected| format!("{:.2}", expected));
+        let mark = if regressed { tr("gauntlet.regressed_mark") } else { "" };
+        println!("{}", format!("{:<16}{:>7.2}{:>10}  {}", opponent.name, score, expected, mark).trim_end());
+        regressions += usize::from(regressed);
+        scores.push((opponent.name.clone(), score));
+    }
+    if let Some(path) = export_path {
+        let columns = vec!["agent", "opponent", "games", "score", "baseline", "regressed"];
+        write_export(&path, &records, &Table { columns, rows: summary })?;
+    }
+
+    if save {
+        let mut text = format!("agent = {}\ngames = {}\nseed = {}\n", agent.name, games, seed);
+        for (name, score) in &scores {
+            text.push_str(&format!("score.{} = {:.2}\n", name, score));
+        }
+        fs::write(&baseline_path, text).map_err(|_| IoError::new(&tr_args("error.write_file", &[("path", &baseline_path)])))?;
+        println!("{}", tr_args("gauntlet.saved", &[("path", &baseline_path)]));
+    } else if baseline.is_none() {
+        println!("{}", tr_args("gauntlet.no_baseline", &[("path", &baseline_path)]));
+    } else if regressions > 0 {
+        return Err(ParseError::new(&tr_args("error.gauntlet_regressed", &[("agent", &agent.name), ("count", &regressions)])).into());
+    } else {
+        println!("{}", tr_args("gauntlet.passed", &[("agent", &agent.name)]));
+    }
+    Ok(())
+}
+
+/// The agents named, or every agent of the config file and every difficulty that plays on its own.
+fn agents(names: Option<Vec<String>>) -> Result<Vec<Agent>, GameError> {
+    let config = Config::load()?;
+    let options = Options::new(&[], &config)?;
+    let names = names.unwrap_or_else(|| {
+        let difficulties = Difficulty::ALL.into_iter().filter(|difficulty| *difficulty != Difficulty::Adaptive).map(|difficulty| difficulty.name().to_string());
+        config.sections("agent").into_iter().chain(difficulties).collect()
+    });
+    names
+        .iter()
+        .map(|name| match parse_seat(name, &config, &options)? {
+            Seat::Bot(agent) => Ok(agent),
+            Seat::Human => Err(ParseError::new(tr("error.tournament_human")).into()),
+        })
+        .collect()
+}
+
+/// Pairs the entrants for the next round: the leaders first, each with the next one down the standings they
+/// haven't met, or the next one down if they have met all of them.
+fn pair(entrants: &[Entrant]) -> Vec<Pairing> {
+    let mut unpaired = ranking(entrants);
+    let bye = (unpaired.len() % 2 == 1).then(|| {
+        let bye = unpaired.iter().rposition(|&index| !entrants[index].had_bye).unwrap_or(unpaired.len() - 1);
+        unpaired.remove(bye)
+    });
+    let mut pairings = Vec::new();
+    while !unpaired.is_empty() {
+        let first = unpaired.remove(0);
+        let opponent = unpaired.iter().position(|index| !entrants[first].opponents.contains(index)).unwrap_or(0);
+        pairings.push(Pairing { first, second: Some(unpaired.remove(opponent)), scores: (0.0, 0.0) });
+    }
+    pairings.extend(bye.map(|first| Pairing { first, second: None, scores: (0.0, 0.0) }));
+    pairings
+}
+
+/// The total score of the opponents an entrant has met.
+fn buchholz(entrants: &[Entrant], index: usize) -> f64 {
+    entrants[index].opponents.iter().map(|&opponent| entrants[opponent].score).sum()
+}
+
+/// The entrants from first to last, by score, then Buchholz, then the order they were entered in.
+fn ranking(entrants: &[Entrant]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..entrants.len()).collect();
+    order.sort_by(|&a, &b| {
+        entrants[b].score.total_cmp(&entrants[a].score).then(buchholz(entrants, b).total_cmp(&buchholz(entrants, a))).then(a.cmp(&b))
+    });
+    order
+}
+
+fn print_standings(entrants: &[Entrant]) {
+    println!("{:<4}{:<16}{:>7}{:>10}", "", tr("tournament.agent"), tr("tournament.score"), tr("tournament.buchholz"));
+    for (rank, &index) in ranking(entrants).iter().enumerate() {
+        let entrant = &entrants[index];
+        println!("{:<4}{:<16}{:>7.1}{:>10.1}", format!("{}.", rank + 1), entrant.agent.name, entrant.score, buchholz(entrants, index));
+    }
+}
+
+/// A game waiting for a worker to play it.
+struct GameJob<'a> {
+    o: &'a Agent,
+    x: &'a Agent,
+    seed: u64,
+    round: Option<usize>,
+}
+
+/// The games of a match between two agents, each moving first in turn, the first agent in the first game.
+/// Game `n` is seeded with `seed + 2n`.
+fn match_games<'a>(first: &'a Agent, second: &'a Agent, games: u64, seed: u64, round: Option<usize>) -> Vec<GameJob<'a>> {
+    (0..games)
+        .map(|game| {
+            let (o, x) = if game % 2 == 0 { (first, second) } else { (second, first) };
+            GameJob { o, x, seed: seed.wrapping_add(game * 2), round }
+        })
+        .collect()
+}
+
+/// The points each agent scored in the games of a match, played in the order `match_games` gives them.
+fn match_scores(games: &[GameRecord]) -> (f64, f64) {
+    let mut scores = (0.0, 0.0);
+    for (game, record) in games.iter().enumerate() {
+        let first_plays_o = game % 2 == 0;
+        match record.winner {
+            Winner::Draw => {
+                scores.0 += 0.5;
+                scores.1 += 0.5;
+            }
+            Winner::Player(winner) if (winner == Player::O) == first_plays_o => scores.0 += 1.0,
+            Winner::Player(_) => scores.1 += 1.0,
+        }
+    }
+    scores
+}
+
+/// Plays the games on up to `workers` threads, each taking the next game left when it is done with one.
+/// The records come back in the order of the games.
+fn play_games(jobs: Vec<GameJob>, workers: usize) -> Result<Vec<GameRecord>, GameError> {
+    let next = AtomicUsize::new(0);
+    let results: Vec<OnceLock<Result<GameRecord, GameError>>> = jobs.iter().map(|_| OnceLock::new()).collect();
+    thread::scope(|scope| {
+        for _ in 0..workers.min(jobs.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else { break };
+                let _ = results[index].set(play_game(job.o, job.x, job.seed, job.round));
+            });
+        }
+    });
+    results.into_iter().flat_map(OnceLock::into_inner).collect()
+}
+
+/// One worker per core, or just the one if that can't be told. Also just the one for agents on a time
+/// budget, so that they don't play each other with a share of a core each.
+fn default_workers<'a>(mut agents: impl Iterator<Item = &'a Agent>) -> usize {
+    if agents.any(|agent| agent.time_budget.is_some()) {
+        return 1;
+    }
+    thread::available_parallelism().map_or(1, |count| count.get())
+}
+
+/// Plays one game between two agents, with bots seeded from the seed given.
+fn play_game(o: &Agent, x: &Agent, seed: u64, round: Option<usize>) -> Result<GameRecord, GameError> {
+    let mut bots = [Bot::new(Player::X, &bot_seed(x, Player::X, Some(seed)))?, Bot::new(Player::O, &bot_seed(o, Player::O, Some(seed)))?];
+    let mut board = Board::new();
+    while board.winner.is_none() {
+        let bot = &mut bots[board.turn as usize];
+        let (coord, _) = choose_bot_move(&mut board, bot);
+        play(&mut board, coord);
+    }
+    Ok(GameRecord {
+        round,
+        o: o.name.clone(),
+        x: x.name.clone(),
+        winner: board.winner.unwrap_or(Winner::Draw),
+        moves: board.history[..board.moves].iter().flatten().map(|&(_, coord)| coord).collect(),
+        seed,
+    })
+}
+
+/// Writes the games and the summary for `--export`.
+fn write_export(path: &str, records: &[GameRecord], summary: &Table) -> Result<(), GameError> {
+    let columns = vec!["game", "round", "o", "x", "winner", "plies", "moves", "seed"];
+    let rows = records
+        .iter()
+        .enumerate()
+        .map(|(number, record)| {
+            let winner = match record.winner {
+                Winner::Player(player) => player.to_string(),
+                Winner::Draw => "draw".to_string(),
+            };
+            let moves: Vec<String> = record.moves.iter().map(Coordinate::to_string).collect();
+            vec![
+                Value::Number((number + 1) as f64),
+                record.round.map_or(Value::Missing, |round| Value::Number(round as f64)),
+                Value::Text(record.o.clone()),
+                Value::Text(record.x.clone()),
+                Value::Text(winner),
+                Value::Number(record.moves.len() as f64),
+                Value::Text(moves.join(" ")),
+                Value::Text(record.seed.to_string()),
+            ]
+        })
+        .collect();
+    for path in export::write(path, &Table { columns, rows }, summary)? {
+        println!("{}", tr_args("tournament.written", &[("path", &path)]));
+    }
+    Ok(())
+}
+
+/// The results round by round, a section for each pairing, then the final score of each agent.
+fn to_config(entrants: &[Entrant], results: &[Vec<Pairing>]) -> String {
+    let mut text = String::new();
+    for (round, pairings) in results.iter().enumerate() {
+        for (number, pairing) in pairings.iter().enumerate() {
+            text.push_str(&format!("[round.{}.{}]\nfirst = {}\n", round + 1, number + 1, entrants[pairing.first].agent.name));
+            match pairing.second {
+                Some(second) => text.push_str(&format!("second = {}\nscore = {}-{}\n", entrants[second].agent.name, pairing.scores.0, pairing.scores.1)),
+                None => text.push_str("second = bye\n"),
+            }
+        }
+    }
+    for (rank, &index) in ranking(entrants).iter().enumerate() {
+        let entrant = &entrants[index];
+        text.push_str(&format!("[standing.{}]\nagent = {}\nscore = {}\nbuchholz = {}\n", rank + 1, entrant.agent.name, entrant.score, buchholz(entrants, index)));
+    }
+    text
+}
@@ -0,0 +1,53 @@
+//! A record of the session in a text file, chosen with `--transcript <file>`: every board, prompt and
+//! engine comment printed, and every line typed, after a `> `. The file is appended to, a line with the
+//! date starting each session, and colors and screen clearing are left out so that it reads anywhere.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::archive::{format_date, seconds};
+use crate::error::IoError;
+use crate::i18n::tr_args;
+
+static FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Starts recording the rest of the run to the end of the file.
+pub fn start(path: &str) -> Result<(), IoError> {
+    let error = || IoError::new(&tr_args("error.write_file", &[("path", &path)]));
+    let mut file = OpenOptions::new().create(true).append(true).open(path).map_err(|_| error())?;
+    writeln!(file, "--- {} ---", format_date(seconds(SystemTime::now()))).map_err(|_| error())?;
+    *FILE.lock().map_err(|_| error())? = Some(file);
+    Ok(())
+}
+
+/// Adds what was printed, if a transcript is being recorded.
+pub fn record(text: &str) {
+    if let Ok(mut file) = FILE.lock() {
+        if let Some(file) = file.as_mut() {
+            // A transcript that can't be written to is not worth stopping the game for
+            let _ = writeln!(file, "{}", plain(text));
+        }
+    }
+}
+
+/// Adds a line the player typed, without its line break.
+pub fn record_input(line: &str) {
+    record(&format!("> {}", line.trim_end_matches(['\r', '\n'])));
+}
+
+/// The text without its ANSI escape sequences.
+fn plain(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            // Up to and including the letter that ends the sequence
+            chars.find(char::is_ascii_alphabetic);
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
@@ -0,0 +1,81 @@
+use crate::analysis::{fork_cells, format_cells, lines_through, winning_cells};
+use crate::i18n::tr_args;
+use crate::{Board, Player};
+
+/// Explains the move the player just made, given the positions before and after it.
+pub fn comment_on_move(before: &Board, after: &Board, player: Player) -> Vec<String> {
+    let Some(played) = before.diff(after).iter().find(|change| change.before.is_none() && change.after.is_some()).map(|change| change.coord) else {
+        return Vec::new();
+    };
+    let cell = played.index();
+    if after.winning_line().is_some() {
+        // The result line says it all
+        return Vec::new();
+    }
+
+    let mut comments = Vec::new();
+    let name = player.name();
+    let opponent = player.opponent().name();
+
+    let missed_wins = winning_cells(before, player);
+    if !missed_wins.is_empty() {
+        comments.push(tr_args("tutorial.missed_win", &[("player", &name), ("cells", &format_cells(&missed_wins))]));
+    }
+
+    let threats = winning_cells(before, player.opponent());
+    if threats.contains(&cell) {
+        comments.push(tr_args("tutorial.blocked", &[("player", &name), ("move", &played)]));
+    } else if !threats.is_empty() {
+        comments.push(tr_args(
+            "tutorial.missed_block",
+            &[("player", &name), ("opponent", &opponent), ("cells", &format_cells(&threats))],
+        ));
+    }
+
+    if comments.is_empty() {
+        let key = match lines_through(cell) {
+            4 => "tutorial.center",
+            3 => "tutorial.corner",
+            _ => "tutorial.edge",
+        };
+        comments.push(tr_args(key, &[("player", &name)]));
+    }
+
+    let new_threats = winning_cells(after, player);
+    if new_threats.len() >= 2 {
+        comments.push(tr_args("tutorial.fork", &[("player", &name), ("cells", &format_cells(&new_threats))]));
+    } else if !new_threats.is_empty() {
+        comments.push(tr_args(
+            "tutorial.threat",
+            &[("player", &name), ("opponent", &opponent), ("cells", &format_cells(&new_threats))],
+        ));
+    }
+
+    comments
+}
+
+/// Advice for the player about to move, most urgent first.
+pub fn advise(board: &Board, player: Player) -> Option<String> {
+    let opponent = player.opponent().name();
+
+    let wins = winning_cells(board, player);
+    if !wins.is_empty() {
+        return Some(tr_args("tutorial.advice_win", &[("cells", &format_cells(&wins))]));
+    }
+    let threats = winning_cells(board, player.opponent());
+    if !threats.is_empty() {
+        return Some(tr_args("tutorial.advice_block", &[("opponent", &opponent), ("cells", &format_cells(&threats))]));
+    }
+    let forks = fork_cells(board, player);
+    if !forks.is_empty() {
+        return Some(tr_args("tutorial.advice_fork", &[("cells", &format_cells(&forks))]));
+    }
+    let opponent_forks = fork_cells(board, player.opponent());
+    if !opponent_forks.is_empty() {
+        return Some(tr_args(
+            "tutorial.advice_prevent_fork",
+            &[("opponent", &opponent), ("cells", &format_cells(&opponent_forks))],
+        ));
+    }
+    None
+}